@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde::de::DeserializeSeed;
+use serde_json::Value;
+use skippable_map::{SkippableMap, SkippableMapSeed};
+
+/// A map with a few small entries which are wanted, among many large ones which are not
+fn input() -> String {
+    let blob: Vec<_> = (0..1000)
+        .map(|i| format!(r#"{{"id": {i}, "name": "item {i}", "tags": ["a", "b", "c"]}}"#))
+        .collect();
+    let blob = format!("[{}]", blob.join(","));
+    let mut entries: Vec<_> = (0..100).map(|i| format!(r#""blob_{i}": {blob}"#)).collect();
+    entries.extend((0..5).map(|i| format!(r#""wanted_{i}": {{"id": {i}}}"#)));
+    format!("{{{}}}", entries.join(","))
+}
+
+fn key_filter(c: &mut Criterion) {
+    let json = input();
+    let wanted = |key: &String| key.starts_with("wanted_");
+
+    let mut group = c.benchmark_group("key_filter");
+    group.sample_size(20);
+    group.bench_function("decode_then_filter", |b| {
+        b.iter(|| {
+            let mut map: SkippableMap<String, Value> =
+                serde_json::from_str(black_box(&json)).unwrap();
+            map.0.retain(|key, _| wanted(key));
+            map
+        })
+    });
+    group.bench_function("filter_keys", |b| {
+        b.iter(|| {
+            SkippableMapSeed::<String, Value>::new()
+                .filter_keys(wanted)
+                .deserialize(&mut serde_json::Deserializer::from_str(black_box(&json)))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, key_filter);
+criterion_main!(benches);
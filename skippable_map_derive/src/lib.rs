@@ -0,0 +1,286 @@
+//! Derive macro for [`skippable_map`](https://docs.rs/skippable_map), which should be used through
+//! its `derive` feature rather than directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Expr, Fields, Ident, LitStr, Member, Result,
+};
+
+/// Derives [`Deserialize`](serde::Deserialize) for a struct with named fields, where a field whose
+/// value does not conform falls back to a default rather than failing the whole struct.
+///
+/// Values fall back on the same terms as `SkippableMap` skips entries. Unknown fields are ignored,
+/// and a missing field falls back too unless it is an `Option`, which is `None` as normal.
+///
+/// The following attributes are supported on fields:
+///
+/// - `#[skippable(default = expr)]`: fall back to `expr` rather than `Default::default()`
+/// - `#[skippable(rename = "name")]`: read the field from the entry with key `name`
+/// - `#[skippable(report)]`: on a field of type `SkipReport<String>`, which is not read from the
+///   input but receives a `SkippedEntry` for each field which fell back, with the key it is read
+///   from and the position of its entry in the input, or the number of entries in the input if it
+///   is missing
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{SkipReport, SkippableDeserialize};
+///
+/// #[derive(SkippableDeserialize)]
+/// struct Config {
+///     name: String,
+///     #[skippable(default = 3)]
+///     retries: u64,
+///     #[skippable(rename = "host-names")]
+///     hosts: Vec<String>,
+///     timeout: Option<u64>,
+///     #[skippable(report)]
+///     skipped: SkipReport<String>,
+/// }
+///
+/// let json = r#"{ "name": "config", "retries": -1, "host-names": ["a", 1], "extra": true }"#;
+/// let config: Config = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(config.name, "config");
+/// assert_eq!(config.retries, 3);
+/// assert!(config.hosts.is_empty());
+/// assert_eq!(config.timeout, None);
+///
+/// let fell_back: Vec<_> = config.skipped.iter().map(|entry| entry.key.as_deref()).collect();
+/// assert_eq!(fell_back, [Some("retries"), Some("host-names")]);
+/// ```
+#[proc_macro_derive(SkippableDeserialize, attributes(skippable))]
+pub fn derive_skippable_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `Partition` and [`Deserialize`](serde::Deserialize) for a struct whose fields are
+/// partitions, such as `SkippableMap`s, so that they are filled from the same map in a single pass
+/// as with `Partitioned`.
+///
+/// Each entry goes to the first field, in the order they are declared, that it conforms to, and
+/// entries which conform to none of them are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{SkippableMap, SkippablePartitions};
+///
+/// #[derive(SkippablePartitions)]
+/// struct ByType {
+///     numbers: SkippableMap<String, u64>,
+///     strings: SkippableMap<String, String>,
+/// }
+///
+/// let json = r#"{ "a": 1, "b": "x", "c": [2], "d": 3 }"#;
+/// let by_type: ByType = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(by_type.numbers.0.len(), 2);
+/// assert_eq!(by_type.strings.0["b"], "x");
+/// ```
+#[proc_macro_derive(SkippablePartitions)]
+pub fn derive_skippable_partitions(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_partitions(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The options given to a field by `#[skippable(...)]`
+#[derive(Default)]
+struct FieldOptions {
+    default: Option<Expr>,
+    rename: Option<String>,
+    report: bool,
+}
+
+impl FieldOptions {
+    fn parse(field: &syn::Field) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("skippable"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    options.default = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("report") {
+                    options.report = true;
+                } else {
+                    return Err(meta.error("expected `default`, `rename` or `report`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "SkippableDeserialize only supports structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SkippableDeserialize only supports structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let report = Ident::new("__report", proc_macro2::Span::mixed_site());
+
+    let mut field_names = Vec::new();
+    let mut bindings = Vec::new();
+    let mut bounds = Vec::new();
+    let mut report_field = None;
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+    for field in fields {
+        let options = FieldOptions::parse(field)?;
+        let ident = field.ident.as_ref().expect("named field");
+        idents.push(ident);
+        if options.report {
+            if report_field.is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "only one field may be marked `#[skippable(report)]`",
+                ));
+            }
+            report_field = Some(ident);
+            values.push(quote!(#report));
+            continue;
+        }
+
+        let index = field_names.len();
+        let binding = format_ident!("__field{}", index);
+        let ty = &field.ty;
+        let default = match options.default {
+            Some(expr) => quote!(#expr),
+            None => {
+                bounds.push(quote!(#ty: ::std::default::Default));
+                quote!(::std::default::Default::default())
+            }
+        };
+        bindings.push(quote! {
+            let #binding: #ty = __fields
+                .take(#index, &mut #report)
+                .unwrap_or_else(|| #default);
+        });
+        bounds.push(quote!(#ty: ::skippable_map::__private::Deserialize<'de>));
+        field_names.push(
+            options
+                .rename
+                .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_owned()),
+        );
+        values.push(quote!(#binding));
+    }
+
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('de));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    where_clause.predicates.extend(
+        bounds
+            .into_iter()
+            .map(|bound| -> syn::WherePredicate { parse_quote!(#bound) }),
+    );
+
+    Ok(quote! {
+        impl #impl_generics ::skippable_map::__private::Deserialize<'de> for #name #ty_generics
+        #where_clause
+        {
+            fn deserialize<__D>(__deserializer: __D) -> ::std::result::Result<Self, __D::Error>
+            where
+                __D: ::skippable_map::__private::Deserializer<'de>,
+            {
+                const FIELDS: &[&str] = &[#(#field_names),*];
+                let __fields =
+                    ::skippable_map::__private::deserialize_fields(__deserializer, #name_str, FIELDS)?;
+                let mut #report = ::skippable_map::SkipReport::<::std::string::String>::default();
+                #(#bindings)*
+                ::std::result::Result::Ok(#name {
+                    #(#idents: #values),*
+                })
+            }
+        }
+    })
+}
+
+fn expand_partitions(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "SkippablePartitions only supports structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let members: Vec<Member> = fields.members().collect();
+    let bounds = fields
+        .iter()
+        .map(|field| &field.ty)
+        .map(|ty| -> syn::WherePredicate { parse_quote!(#ty: ::skippable_map::Partition<'de>) });
+
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('de));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    where_clause.predicates.extend(bounds);
+
+    Ok(quote! {
+        impl #impl_generics ::skippable_map::Partition<'de> for #name #ty_generics
+        #where_clause
+        {
+            fn empty() -> Self {
+                #name {
+                    #(#members: ::skippable_map::Partition::empty()),*
+                }
+            }
+
+            fn offer(
+                &mut self,
+                __entry: ::skippable_map::__private::PartitionEntry<'_, 'de>,
+            ) -> ::std::result::Result<bool, ::skippable_map::DuplicateKey> {
+                ::std::result::Result::Ok(
+                    false #(|| ::skippable_map::Partition::offer(&mut self.#members, __entry)?)*
+                )
+            }
+        }
+
+        impl #impl_generics ::skippable_map::__private::Deserialize<'de> for #name #ty_generics
+        #where_clause
+        {
+            fn deserialize<__D>(__deserializer: __D) -> ::std::result::Result<Self, __D::Error>
+            where
+                __D: ::skippable_map::__private::Deserializer<'de>,
+            {
+                ::skippable_map::__private::deserialize_partitions(__deserializer)
+            }
+        }
+    })
+}
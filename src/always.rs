@@ -0,0 +1,93 @@
+//! A map which deserializes from any input.
+
+use crate::{
+    content::{Content, ContentRefDeserializer},
+    DuplicatePolicy, LastWins, SkipError, SkippableMap,
+};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+
+/// A [`SkippableMap`] which never fails because the input is not a map: anything else, such as
+/// `null`, a string or a number, deserializes to an empty map, and the reason is kept in `error`.
+///
+/// This is useful as a field of a struct which should still deserialize when the field has the
+/// wrong shape altogether, though [broken input](SkippableMap#errors) is still an error, as is a
+/// map rejected by the [`DuplicatePolicy`] `P`. The input is buffered before it is decoded, so it
+/// is read only once.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_json;
+/// use skippable_map::AlwaysSkippableMap;
+///
+/// #[derive(Deserialize)]
+/// struct Data {
+///     limits: AlwaysSkippableMap<String, u64>,
+///     names: AlwaysSkippableMap<String, String>,
+/// }
+///
+/// let json = r#"{ "limits": "none", "names": { "a": "b", "c": 1 } }"#;
+/// let data: Data = serde_json::from_str(json).unwrap();
+///
+/// assert!(data.limits.map.0.is_empty());
+/// assert!(data.limits.error.is_some());
+/// assert_eq!(data.names.map.0.len(), 1);
+/// assert!(data.names.error.is_none());
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct AlwaysSkippableMap<K, V, M = HashMap<K, V>, P = LastWins> {
+    /// Entries which decoded to `(K, V)`
+    pub map: SkippableMap<K, V, M, P>,
+    /// Why the input could not be read as a map, if it could not
+    #[serde(skip)]
+    pub error: Option<SkipError>,
+}
+
+impl<K, V, M, P> AlwaysSkippableMap<K, V, M, P> {
+    /// Returns the wrapped inner map, consuming self
+    pub fn inner(self) -> M {
+        self.map.0
+    }
+
+    /// Splits into the [`SkippableMap`] of kept entries and the reason the input was not a map
+    pub fn into_parts(self) -> (SkippableMap<K, V, M, P>, Option<SkipError>) {
+        (self.map, self.error)
+    }
+}
+
+impl<K, V, M, P> AsRef<M> for AlwaysSkippableMap<K, V, M, P> {
+    fn as_ref(&self) -> &M {
+        &self.map.0
+    }
+}
+
+impl<'de, K, V, M, P> Deserialize<'de> for AlwaysSkippableMap<K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = Content::deserialize(deserializer)?;
+        match content {
+            Content::Map(_) => Ok(AlwaysSkippableMap {
+                map: SkippableMap::deserialize(ContentRefDeserializer::<D::Error>::new(&content))?,
+                error: None,
+            }),
+            // Anything else is not a map, even if it would otherwise decode to an empty one
+            _ => Ok(AlwaysSkippableMap {
+                map: SkippableMap::new(P::new_map(0)),
+                error: Some(SkipError::new(D::Error::invalid_type(
+                    content.unexpected(),
+                    &"a map",
+                ))),
+            }),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Values whose type is coerced from the other forms it is commonly written in.
+
+use crate::content::{Content, ContentRefDeserializer};
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::marker::PhantomData;
+
+/// A wrapper around a number or `bool` which also accepts the value written in other forms, as
+/// some producers of data do not keep to one type for a field:
+///
+/// - numbers written as strings, e.g. `"42"` or `"4.2"`
+/// - integers written as floats with no fractional part, e.g. `42.0`, or as strings of them
+/// - booleans written as the strings `"true"` and `"false"`
+///
+/// Integers which are out of range for the type, floats with a fractional part and strings which
+/// do not parse to a finite number still fail, so an entry of a [`SkippableMap`](crate::SkippableMap) with this as
+/// its value is skipped only when coercion fails. `Option<T>` may be used for `T` to also accept
+/// `null`. Other types are decoded as normal.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{Coerce, SkippableMap};
+///
+/// let json = r#"{ "a": 42, "b": "42", "c": 42.0, "d": 4.2, "e": "x" }"#;
+/// let map: SkippableMap<String, Coerce<u64>> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(map.0.len(), 3);
+/// assert!(map.0.values().all(|value| value.0 == 42));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Coerce<T>(pub T);
+
+impl<T> Coerce<T> {
+    /// Returns the wrapped value, consuming self
+    pub fn inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> AsRef<T> for Coerce<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Coerce<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = Content::deserialize(deserializer)?;
+        T::deserialize(CoerceRefDeserializer::<D::Error>::new(&content)).map(Coerce)
+    }
+}
+
+/// Deserializes a scalar from a borrowed [`Content`].
+///
+/// This behaves as [`ContentRefDeserializer`], except that numbers and booleans may also be
+/// decoded from strings by parsing them, and integers from floats with no fractional part.
+struct CoerceRefDeserializer<'a, 'de, E> {
+    content: &'a Content<'de>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> CoerceRefDeserializer<'a, 'de, E> {
+    fn new(content: &'a Content<'de>) -> Self {
+        Self {
+            content,
+            err: PhantomData,
+        }
+    }
+
+    fn content(&self) -> ContentRefDeserializer<'a, 'de, E> {
+        ContentRefDeserializer::new(self.content)
+    }
+}
+
+/// Returns `f` as an integer of type `$ty` if it has no fractional part and is in range
+macro_rules! integral {
+    ($f:expr, $ty:ty) => {{
+        let f: f64 = $f;
+        // `MAX + 1` is a power of two, so unlike `MAX` it converts to `f64` exactly
+        (f.fract() == 0.0 && f >= <$ty>::MIN as f64 && f < <$ty>::MAX as f64 + 1.0)
+            .then(|| f as $ty)
+    }};
+}
+
+macro_rules! deserialize_coerced_integer {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, E>
+            where
+                V: Visitor<'de>,
+            {
+                let coerced = match *self.content {
+                    Content::F32(f) => integral!(f as f64, $ty),
+                    Content::F64(f) => integral!(f, $ty),
+                    Content::String(_) | Content::Str(_) => {
+                        let s = self.content.as_str().unwrap_or_default();
+                        match s.parse() {
+                            Ok(parsed) => Some(parsed),
+                            Err(_) => s.parse().ok().and_then(|f| integral!(f, $ty)),
+                        }
+                    }
+                    _ => return self.content().$method(visitor),
+                };
+                match coerced {
+                    Some(n) => visitor.$visit(n),
+                    None => Err(de::Error::invalid_value(self.content.unexpected(), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! deserialize_coerced_float {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, E>
+            where
+                V: Visitor<'de>,
+            {
+                match self.content.as_str() {
+                    // Strings such as "NaN" and "inf", or too large for the type, are not numbers
+                    Some(s) => match s.parse::<$ty>() {
+                        Ok(parsed) if parsed.is_finite() => visitor.$visit(parsed),
+                        _ => Err(de::Error::invalid_value(Unexpected::Str(s), &visitor)),
+                    },
+                    None => self.content().$method(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, E> Deserializer<'de> for CoerceRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.content().deserialize_any(visitor)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.content.as_str() {
+            Some("true") => visitor.visit_bool(true),
+            Some("false") => visitor.visit_bool(false),
+            Some(s) => Err(de::Error::invalid_value(Unexpected::Str(s), &visitor)),
+            None => self.content().deserialize_bool(visitor),
+        }
+    }
+
+    deserialize_coerced_integer! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+    }
+
+    deserialize_coerced_float! {
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match *self.content {
+            Content::None | Content::Unit => visitor.visit_none(),
+            Content::Some(ref v) => visitor.visit_some(CoerceRefDeserializer::new(v)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match *self.content {
+            Content::Newtype(ref v) => visitor.visit_newtype_struct(CoerceRefDeserializer::new(v)),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.content().deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
@@ -0,0 +1,757 @@
+//! A buffered, format-independent representation of a single value.
+//!
+//! Reading a value into [`Content`] only fails if the input itself is broken (a syntax error,
+//! unexpected EOF, an I/O error, ...), as any well-formed value can be represented. Decoding the
+//! buffered value into the requested type is then a separate step, and failures there mean the
+//! value is well-formed but does not conform, so it can be skipped safely.
+
+use crate::size_hint;
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+    Serialize, Serializer,
+};
+use std::{fmt, marker::PhantomData};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Content<'de> {
+    Bool(bool),
+
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+
+    F32(f32),
+    F64(f64),
+
+    Char(char),
+    String(String),
+    Str(&'de str),
+    ByteBuf(Vec<u8>),
+    Bytes(&'de [u8]),
+
+    None,
+    Some(Box<Content<'de>>),
+
+    Unit,
+    Newtype(Box<Content<'de>>),
+    Seq(Vec<Content<'de>>),
+    Map(Vec<(Content<'de>, Content<'de>)>),
+}
+
+impl<'de> Content<'de> {
+    pub(crate) fn unexpected(&self) -> Unexpected<'_> {
+        match *self {
+            Content::Bool(b) => Unexpected::Bool(b),
+            Content::U8(n) => Unexpected::Unsigned(n as u64),
+            Content::U16(n) => Unexpected::Unsigned(n as u64),
+            Content::U32(n) => Unexpected::Unsigned(n as u64),
+            Content::U64(n) => Unexpected::Unsigned(n),
+            Content::U128(_) => Unexpected::Other("128-bit integer"),
+            Content::I8(n) => Unexpected::Signed(n as i64),
+            Content::I16(n) => Unexpected::Signed(n as i64),
+            Content::I32(n) => Unexpected::Signed(n as i64),
+            Content::I64(n) => Unexpected::Signed(n),
+            Content::I128(_) => Unexpected::Other("128-bit integer"),
+            Content::F32(f) => Unexpected::Float(f as f64),
+            Content::F64(f) => Unexpected::Float(f),
+            Content::Char(c) => Unexpected::Char(c),
+            Content::String(ref s) => Unexpected::Str(s),
+            Content::Str(s) => Unexpected::Str(s),
+            Content::ByteBuf(ref b) => Unexpected::Bytes(b),
+            Content::Bytes(b) => Unexpected::Bytes(b),
+            Content::None | Content::Some(_) => Unexpected::Option,
+            Content::Unit => Unexpected::Unit,
+            Content::Newtype(_) => Unexpected::NewtypeStruct,
+            Content::Seq(_) => Unexpected::Seq,
+            Content::Map(_) => Unexpected::Map,
+        }
+    }
+
+    /// Returns the string this holds, if any
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match *self {
+            Content::String(ref s) => Some(s),
+            Content::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Copies any data borrowed from the input, so that the value may outlive it
+    pub(crate) fn to_static(&self) -> Content<'static> {
+        match *self {
+            Content::Bool(v) => Content::Bool(v),
+            Content::U8(v) => Content::U8(v),
+            Content::U16(v) => Content::U16(v),
+            Content::U32(v) => Content::U32(v),
+            Content::U64(v) => Content::U64(v),
+            Content::U128(v) => Content::U128(v),
+            Content::I8(v) => Content::I8(v),
+            Content::I16(v) => Content::I16(v),
+            Content::I32(v) => Content::I32(v),
+            Content::I64(v) => Content::I64(v),
+            Content::I128(v) => Content::I128(v),
+            Content::F32(v) => Content::F32(v),
+            Content::F64(v) => Content::F64(v),
+            Content::Char(v) => Content::Char(v),
+            Content::String(ref v) => Content::String(v.clone()),
+            Content::Str(v) => Content::String(v.to_owned()),
+            Content::ByteBuf(ref v) => Content::ByteBuf(v.clone()),
+            Content::Bytes(v) => Content::ByteBuf(v.to_owned()),
+            Content::None => Content::None,
+            Content::Some(ref v) => Content::Some(Box::new(v.to_static())),
+            Content::Unit => Content::Unit,
+            Content::Newtype(ref v) => Content::Newtype(Box::new(v.to_static())),
+            Content::Seq(ref v) => Content::Seq(v.iter().map(Content::to_static).collect()),
+            Content::Map(ref v) => Content::Map(
+                v.iter()
+                    .map(|(key, value)| (key.to_static(), value.to_static()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'de> Serialize for Content<'de> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            Content::Bool(v) => serializer.serialize_bool(v),
+            Content::U8(v) => serializer.serialize_u8(v),
+            Content::U16(v) => serializer.serialize_u16(v),
+            Content::U32(v) => serializer.serialize_u32(v),
+            Content::U64(v) => serializer.serialize_u64(v),
+            Content::U128(v) => serializer.serialize_u128(v),
+            Content::I8(v) => serializer.serialize_i8(v),
+            Content::I16(v) => serializer.serialize_i16(v),
+            Content::I32(v) => serializer.serialize_i32(v),
+            Content::I64(v) => serializer.serialize_i64(v),
+            Content::I128(v) => serializer.serialize_i128(v),
+            Content::F32(v) => serializer.serialize_f32(v),
+            Content::F64(v) => serializer.serialize_f64(v),
+            Content::Char(v) => serializer.serialize_char(v),
+            Content::String(ref v) => serializer.serialize_str(v),
+            Content::Str(v) => serializer.serialize_str(v),
+            Content::ByteBuf(ref v) => serializer.serialize_bytes(v),
+            Content::Bytes(v) => serializer.serialize_bytes(v),
+            Content::None => serializer.serialize_none(),
+            Content::Some(ref v) => serializer.serialize_some(v),
+            Content::Unit => serializer.serialize_unit(),
+            Content::Newtype(ref v) => serializer.serialize_newtype_struct("", v),
+            Content::Seq(ref v) => serializer.collect_seq(v),
+            Content::Map(ref v) => match v.as_slice() {
+                // A TOML datetime is read as a map with a single private field, which is only
+                // written back as a datetime inside a struct of the matching private name
+                [(key, value)] if key.as_str() == Some(TOML_DATETIME_FIELD) => {
+                    let mut state = serializer.serialize_struct(TOML_DATETIME_NAME, 1)?;
+                    state.serialize_field(TOML_DATETIME_FIELD, value)?;
+                    state.end()
+                }
+                _ => serializer.collect_map(v.iter().map(|(k, v)| (k, v))),
+            },
+        }
+    }
+}
+
+/// The names `toml` gives the struct and field which a datetime is (de)serialized as
+const TOML_DATETIME_NAME: &str = "$__toml_private_Datetime";
+const TOML_DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+impl<'de> Deserialize<'de> for Content<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Content::Bool(v))
+    }
+
+    fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E> {
+        Ok(Content::I8(v))
+    }
+
+    fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E> {
+        Ok(Content::I16(v))
+    }
+
+    fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E> {
+        Ok(Content::I32(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Content::I64(v))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E> {
+        Ok(Content::I128(v))
+    }
+
+    fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E> {
+        Ok(Content::U8(v))
+    }
+
+    fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E> {
+        Ok(Content::U16(v))
+    }
+
+    fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E> {
+        Ok(Content::U32(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Content::U64(v))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E> {
+        Ok(Content::U128(v))
+    }
+
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E> {
+        Ok(Content::F32(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Content::F64(v))
+    }
+
+    fn visit_char<E>(self, v: char) -> Result<Self::Value, E> {
+        Ok(Content::Char(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Content::String(v.to_owned()))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(Content::Str(v))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Content::String(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Content::ByteBuf(v.to_vec()))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(Content::Bytes(v))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Content::ByteBuf(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Content::Unit)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Content::None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Content::deserialize(deserializer).map(|v| Content::Some(Box::new(v)))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Content::deserialize(deserializer).map(|v| Content::Newtype(Box::new(v)))
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(size_hint::cautious::<Content>(access.size_hint()));
+        while let Some(element) = access.next_element()? {
+            vec.push(element);
+        }
+        Ok(Content::Seq(vec))
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(size_hint::cautious::<(Content, Content)>(
+            access.size_hint(),
+        ));
+        while let Some(entry) = access.next_entry()? {
+            vec.push(entry);
+        }
+        Ok(Content::Map(vec))
+    }
+}
+
+/// Deserializes a type from a borrowed [`Content`], so that the same buffered value may be decoded
+/// more than once.
+pub(crate) struct ContentRefDeserializer<'a, 'de, E> {
+    content: &'a Content<'de>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> ContentRefDeserializer<'a, 'de, E> {
+    pub(crate) fn new(content: &'a Content<'de>) -> Self {
+        Self {
+            content,
+            err: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, E> Deserializer<'de> for ContentRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match *self.content {
+            Content::Bool(v) => visitor.visit_bool(v),
+            Content::U8(v) => visitor.visit_u8(v),
+            Content::U16(v) => visitor.visit_u16(v),
+            Content::U32(v) => visitor.visit_u32(v),
+            Content::U64(v) => visitor.visit_u64(v),
+            Content::U128(v) => visitor.visit_u128(v),
+            Content::I8(v) => visitor.visit_i8(v),
+            Content::I16(v) => visitor.visit_i16(v),
+            Content::I32(v) => visitor.visit_i32(v),
+            Content::I64(v) => visitor.visit_i64(v),
+            Content::I128(v) => visitor.visit_i128(v),
+            Content::F32(v) => visitor.visit_f32(v),
+            Content::F64(v) => visitor.visit_f64(v),
+            Content::Char(v) => visitor.visit_char(v),
+            Content::String(ref v) => visitor.visit_str(v),
+            Content::Str(v) => visitor.visit_borrowed_str(v),
+            Content::ByteBuf(ref v) => visitor.visit_bytes(v),
+            Content::Bytes(v) => visitor.visit_borrowed_bytes(v),
+            Content::Unit => visitor.visit_unit(),
+            Content::None => visitor.visit_none(),
+            Content::Some(ref v) => visitor.visit_some(ContentRefDeserializer::new(v)),
+            Content::Newtype(ref v) => visitor.visit_newtype_struct(ContentRefDeserializer::new(v)),
+            Content::Seq(ref v) => visit_content_seq(v, visitor),
+            Content::Map(ref v) => visit_content_map(v, visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match *self.content {
+            Content::None | Content::Unit => visitor.visit_none(),
+            Content::Some(ref v) => visitor.visit_some(ContentRefDeserializer::new(v)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match *self.content {
+            Content::Newtype(ref v) => visitor.visit_newtype_struct(ContentRefDeserializer::new(v)),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        let (variant, value) = match *self.content {
+            Content::String(_) | Content::Str(_) => (self.content, None),
+            Content::Map(ref entries) => match entries.as_slice() {
+                [(variant, value)] => (variant, Some(value)),
+                _ => {
+                    return Err(de::Error::invalid_value(
+                        Unexpected::Map,
+                        &"map with a single key",
+                    ))
+                }
+            },
+            ref other => {
+                return Err(de::Error::invalid_type(
+                    other.unexpected(),
+                    &"string or map",
+                ))
+            }
+        };
+        visitor.visit_enum(EnumRefDeserializer {
+            variant,
+            value,
+            err: PhantomData,
+        })
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+/// Deserializes a map key from a borrowed [`Content`].
+///
+/// This behaves as [`ContentRefDeserializer`], except that a string may also be decoded as a
+/// boolean or a number by parsing it, as formats such as JSON and TOML only have string keys.
+pub(crate) struct KeyRefDeserializer<'a, 'de, E> {
+    content: &'a Content<'de>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> KeyRefDeserializer<'a, 'de, E> {
+    pub(crate) fn new(content: &'a Content<'de>) -> Self {
+        Self {
+            content,
+            err: PhantomData,
+        }
+    }
+
+    fn content(&self) -> ContentRefDeserializer<'a, 'de, E> {
+        ContentRefDeserializer::new(self.content)
+    }
+}
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, E>
+            where
+                V: Visitor<'de>,
+            {
+                match *self.content {
+                    Content::String(ref v) => match v.parse() {
+                        Ok(parsed) => visitor.$visit(parsed),
+                        Err(_) => Err(de::Error::invalid_value(Unexpected::Str(v), &visitor)),
+                    },
+                    Content::Str(v) => match v.parse() {
+                        Ok(parsed) => visitor.$visit(parsed),
+                        Err(_) => Err(de::Error::invalid_value(Unexpected::Str(v), &visitor)),
+                    },
+                    _ => self.content().$method(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, E> Deserializer<'de> for KeyRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.content().deserialize_any(visitor)
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.content().deserialize_option(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match *self.content {
+            Content::Newtype(ref v) => visitor.visit_newtype_struct(KeyRefDeserializer::new(v)),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.content().deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+fn visit_content_seq<'a, 'de, V, E>(content: &'a [Content<'de>], visitor: V) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error,
+{
+    let mut seq = SeqRefDeserializer::<E> {
+        iter: content.iter(),
+        err: PhantomData,
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+    match seq.iter.len() {
+        0 => Ok(value),
+        remaining => Err(de::Error::invalid_length(
+            content.len() - remaining,
+            &"fewer elements in sequence",
+        )),
+    }
+}
+
+fn visit_content_map<'a, 'de, V, E>(
+    content: &'a [(Content<'de>, Content<'de>)],
+    visitor: V,
+) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error,
+{
+    let mut map = MapRefDeserializer::<E> {
+        iter: content.iter(),
+        value: None,
+        err: PhantomData,
+    };
+    let value = visitor.visit_map(&mut map)?;
+    match map.iter.len() {
+        0 => Ok(value),
+        remaining => Err(de::Error::invalid_length(
+            content.len() - remaining,
+            &"fewer elements in map",
+        )),
+    }
+}
+
+struct SeqRefDeserializer<'a, 'de, E> {
+    iter: std::slice::Iter<'a, Content<'de>>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> SeqAccess<'de> for SeqRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, E>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => seed
+                .deserialize(ContentRefDeserializer::new(value))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapRefDeserializer<'a, 'de, E> {
+    iter: std::slice::Iter<'a, (Content<'de>, Content<'de>)>,
+    value: Option<&'a Content<'de>>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> MapAccess<'de> for MapRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, E>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(ContentRefDeserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, E>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ContentRefDeserializer::new(value)),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumRefDeserializer<'a, 'de, E> {
+    variant: &'a Content<'de>,
+    value: Option<&'a Content<'de>>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> de::EnumAccess<'de> for EnumRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+    type Variant = VariantRefDeserializer<'a, 'de, E>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), E>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(ContentRefDeserializer::new(self.variant))?;
+        Ok((
+            variant,
+            VariantRefDeserializer {
+                value: self.value,
+                err: PhantomData,
+            },
+        ))
+    }
+}
+
+struct VariantRefDeserializer<'a, 'de, E> {
+    value: Option<&'a Content<'de>>,
+    err: PhantomData<E>,
+}
+
+impl<'a, 'de, E> de::VariantAccess<'de> for VariantRefDeserializer<'a, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        match self.value {
+            None | Some(Content::Unit) => Ok(()),
+            Some(other) => Err(de::Error::invalid_type(other.unexpected(), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(ContentRefDeserializer::new(value)),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Content::Seq(v)) => visit_content_seq(v, visitor),
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"tuple variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Content::Map(v)) => visit_content_map(v, visitor),
+            Some(Content::Seq(v)) => visit_content_seq(v, visitor),
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
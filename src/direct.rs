@@ -0,0 +1,104 @@
+//! Reading maps without buffering their keys and values, for formats which are not
+//! self-describing.
+
+use crate::{expecting_map, seed::Options, size_hint, DuplicatePolicy, Recoverable, SkippableMap};
+use serde::de::{Deserialize, Error, MapAccess, Visitor};
+use std::{any::TypeId, fmt, marker::PhantomData};
+
+/// Visits a map, decoding its keys and values straight from the input, and skipping only the
+/// entries whose error is [`Recoverable`] as the format's error type `E`
+#[allow(clippy::type_complexity)]
+pub(crate) struct DirectVisitor<'a, K, V, M, P, E> {
+    options: Options<'a, K>,
+    marker: PhantomData<fn() -> (SkippableMap<K, V, M, P>, E)>,
+}
+
+impl<'a, K, V, M, P, E> DirectVisitor<'a, K, V, M, P, E> {
+    pub(crate) fn new(options: Options<'a, K>) -> Self {
+        Self {
+            options,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, K, V, M, P, E> Visitor<'de> for DirectVisitor<'a, K, V, M, P, E>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+    E: Recoverable + 'static,
+{
+    type Value = SkippableMap<K, V, M, P>;
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        expecting_map::<K, V>(formatter)
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let Options {
+            key_filter,
+            max_entries,
+            mut skips,
+            ..
+        } = self.options;
+        let recoverable =
+            |error: &A::Error| as_format_error::<_, E>(error).is_some_and(E::is_recoverable);
+        let mut map = P::new_map(size_hint::cautious::<(K, V)>(access.size_hint()));
+        for index in 0.. {
+            let key = match access.next_key::<K>() {
+                // End of data structure (end)
+                Ok(None) => break,
+                Ok(Some(key)) => Ok(key),
+                Err(e) if recoverable(&e) => Err(e),
+                // Error which cannot be skipped (fail)
+                Err(e) => return Err(e),
+            };
+            if let Some(max) = max_entries.filter(|&max| index >= max) {
+                return Err(Error::custom(format_args!(
+                    "map has more than {max} entries"
+                )));
+            }
+            // The value is decoded even if it is not wanted, as a format which does not describe
+            // its own types cannot skip over it otherwise
+            let value = match access.next_value::<V>() {
+                Ok(value) => Ok(value),
+                Err(e) if recoverable(&e) => Err(e),
+                Err(e) => return Err(e),
+            };
+            match (key, value) {
+                // Error in decoding key (skip)
+                (Err(e), _) => skips.record(index, None, e)?,
+                // Key rejected (skip without counting it)
+                (Ok(key), _) if !key_filter.as_ref().is_none_or(|filter| filter(&key)) => {}
+                // Success in decoding entry (insert)
+                (Ok(key), Ok(value)) => {
+                    P::insert(&mut map, key, value).map_err(|error| error.at(index))?
+                }
+                // Error in decoding value (skip)
+                (Ok(key), Err(e)) => skips.record(index, Some(key), e)?,
+            }
+        }
+        Ok(SkippableMap::new(map))
+    }
+}
+
+/// Returns `error` as the error type `E` of the format being read.
+///
+/// serde does not tie the error type of a [`MapAccess`] to that of the deserializer which made it,
+/// though every format uses the same one, so the two are compared at runtime. An error of any
+/// other type is treated as fatal.
+fn as_format_error<T, E>(error: &T) -> Option<&E>
+where
+    E: 'static,
+{
+    if typeid::of::<T>() == TypeId::of::<E>() {
+        // SAFETY: `T` and `E` only differ in lifetimes, and `E` has none as it is `'static`, so
+        // they are the same type
+        Some(unsafe { &*(error as *const T).cast::<E>() })
+    } else {
+        None
+    }
+}
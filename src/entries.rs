@@ -0,0 +1,349 @@
+//! Reading the entries of a JSON object lazily from a reader.
+
+use crate::content::{Content, ContentRefDeserializer, KeyRefDeserializer};
+use serde::de::DeserializeOwned;
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read},
+    iter::FusedIterator,
+    marker::PhantomData,
+};
+
+/// An iterator over the entries of a top-level JSON object read from an [`io::Read`], which yields
+/// those that decode to `(K, V)` as they are read and skips the rest, as
+/// [`SkippableMap`](crate::SkippableMap) does.
+///
+/// Only one entry is held in memory at a time, so objects far too large to collect, such as
+/// multi-gigabyte dumps, can be filtered with memory bounded by the size of their largest entry.
+/// The reader is buffered internally.
+///
+/// If the input itself is broken (e.g. truncated, a syntax error, or an I/O error while reading),
+/// a [`JsonEntriesError`] saying where is yielded and the iterator ends.
+///
+/// # Examples
+///
+/// ```rust
+/// use skippable_map::JsonEntries;
+///
+/// let json = r#"{ "a": 1, "b": "x", "c": 2 }"#;
+/// let entries: Vec<(String, u64)> = JsonEntries::new(json.as_bytes())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(entries, [("a".into(), 1), ("c".into(), 2)]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[allow(clippy::type_complexity)]
+pub struct JsonEntries<R, K, V> {
+    input: Input<R>,
+    state: State,
+    /// The offset of the start of the last key or value read
+    start: u64,
+    key: Vec<u8>,
+    value: Vec<u8>,
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+/// How far through the object the iterator has read
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the opening `{`
+    Start,
+    /// After the opening `{`
+    First,
+    /// After an entry
+    Rest,
+    /// After the closing `}` or an error
+    Done,
+}
+
+impl<R, K, V> JsonEntries<R, K, V>
+where
+    R: Read,
+{
+    /// Creates an iterator over the entries of the object read from `reader`
+    pub fn new(reader: R) -> Self {
+        Self {
+            input: Input {
+                reader: BufReader::new(reader),
+                offset: 0,
+            },
+            state: State::Start,
+            start: 0,
+            key: Vec::new(),
+            value: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the offset in bytes of the start of the last key or value read from the input,
+    /// from which the line and column of an error in it are counted
+    pub fn offset(&self) -> u64 {
+        self.start
+    }
+
+    /// Reads up to and including the next entry which decodes to `(K, V)`, or the end of the object
+    fn read_entry(&mut self) -> Result<Option<(K, V)>, JsonEntriesError>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let input = &mut self.input;
+        if self.state == State::Start {
+            match input.skip_whitespace()? {
+                Some(b'{') => input.consume(),
+                None => return Err(input.eof()),
+                _ => return Err(input.invalid("expected `{`")),
+            }
+            self.state = State::First;
+        }
+        loop {
+            match (input.skip_whitespace()?, self.state) {
+                (Some(b'}'), _) => {
+                    input.consume();
+                    self.state = State::Done;
+                    // As with `serde_json::from_reader`, only whitespace may follow
+                    return match input.skip_whitespace()? {
+                        Some(_) => Err(input.invalid("trailing characters after object")),
+                        None => Ok(None),
+                    };
+                }
+                (Some(b','), State::Rest) => input.consume(),
+                (_, State::First) => {}
+                (None, _) => return Err(input.eof()),
+                _ => return Err(input.invalid("expected `,` or `}`")),
+            }
+            self.state = State::Rest;
+
+            // Errors reading into `Content` are in the input itself, and so are returned, while
+            // errors decoding it mean the entry does not conform, and so it is skipped
+            if input.skip_whitespace()? != Some(b'"') {
+                return Err(input.invalid("expected a string key"));
+            }
+            self.start = input.read_value(&mut self.key)?;
+            let key: Content = parse(&self.key, self.start)?;
+            match input.skip_whitespace()? {
+                Some(b':') => input.consume(),
+                None => return Err(input.eof()),
+                _ => return Err(input.invalid("expected `:`")),
+            }
+            self.start = input.read_value(&mut self.value)?;
+            let value: Content = parse(&self.value, self.start)?;
+
+            let Ok(key) = K::deserialize(KeyRefDeserializer::<serde_json::Error>::new(&key)) else {
+                continue;
+            };
+            if let Ok(value) =
+                V::deserialize(ContentRefDeserializer::<serde_json::Error>::new(&value))
+            {
+                return Ok(Some((key, value)));
+            }
+        }
+    }
+}
+
+impl<R, K, V> Iterator for JsonEntries<R, K, V>
+where
+    R: Read,
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    type Item = Result<(K, V), JsonEntriesError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == State::Done {
+            return None;
+        }
+        let entry = self.read_entry();
+        if entry.is_err() {
+            self.state = State::Done;
+        }
+        entry.transpose()
+    }
+}
+
+impl<R, K, V> FusedIterator for JsonEntries<R, K, V>
+where
+    R: Read,
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+}
+
+/// Parses a key or value read from the input starting at `offset` into `Content`
+fn parse(bytes: &[u8], offset: u64) -> Result<Content<'_>, JsonEntriesError> {
+    serde_json::from_slice(bytes).map_err(|error| JsonEntriesError::Json { error, offset })
+}
+
+/// The error which ends a [`JsonEntries`] iterator when the input itself is broken
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug)]
+pub enum JsonEntriesError {
+    /// The structure of the object is broken, e.g. a `,` is missing or the input ends early
+    Syntax {
+        /// What was wrong
+        message: &'static str,
+        /// The offset in bytes into the input at which it was found
+        offset: u64,
+    },
+    /// A key or value is not well-formed JSON
+    Json {
+        /// The error from parsing the key or value on its own, whose line and column are counted
+        /// from its start
+        error: serde_json::Error,
+        /// The offset in bytes into the input at which the key or value starts
+        offset: u64,
+    },
+    /// Reading from the input failed
+    Io(io::Error),
+}
+
+impl JsonEntriesError {
+    /// Returns the offset in bytes into the input at which the error was found, or at which the
+    /// key or value it is in starts, unless it is an I/O error
+    pub fn offset(&self) -> Option<u64> {
+        match *self {
+            Self::Syntax { offset, .. } | Self::Json { offset, .. } => Some(offset),
+            Self::Io(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for JsonEntriesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { message, offset } => write!(f, "{message} at byte {offset}"),
+            Self::Json { error, offset } => {
+                write!(f, "{error} in the key or value at byte {offset}")
+            }
+            Self::Io(error) => write!(f, "error reading input: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonEntriesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Syntax { .. } => None,
+            Self::Json { error, .. } => Some(error),
+            Self::Io(error) => Some(error),
+        }
+    }
+}
+
+/// The input, which counts the bytes consumed from it so that errors can say where they are
+struct Input<R> {
+    reader: BufReader<R>,
+    offset: u64,
+}
+
+impl<R> Input<R>
+where
+    R: Read,
+{
+    fn invalid(&self, message: &'static str) -> JsonEntriesError {
+        JsonEntriesError::Syntax {
+            message,
+            offset: self.offset,
+        }
+    }
+
+    fn eof(&self) -> JsonEntriesError {
+        self.invalid("unexpected end of input")
+    }
+
+    /// Returns the next byte without consuming it
+    fn peek(&mut self) -> Result<Option<u8>, JsonEntriesError> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().copied()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(JsonEntriesError::Io(e)),
+            }
+        }
+    }
+
+    /// Consumes the byte returned by the last [`peek`](Self::peek)
+    fn consume(&mut self) {
+        self.reader.consume(1);
+        self.offset += 1;
+    }
+
+    /// Consumes and returns the next byte, or returns an error at the end of the input
+    fn next(&mut self) -> Result<u8, JsonEntriesError> {
+        let byte = self.peek()?.ok_or_else(|| self.eof())?;
+        self.consume();
+        Ok(byte)
+    }
+
+    /// Consumes any whitespace, returning the byte after it without consuming it
+    fn skip_whitespace(&mut self) -> Result<Option<u8>, JsonEntriesError> {
+        loop {
+            match self.peek()? {
+                Some(b' ' | b'\n' | b'\t' | b'\r') => self.consume(),
+                byte => return Ok(byte),
+            }
+        }
+    }
+
+    /// Reads the bytes of the next JSON value into `buf`, returning the offset it starts at,
+    /// without checking that it is well-formed beyond finding where it ends: that is left to
+    /// `serde_json`. Nesting is tracked with a counter rather than recursion, so deeply nested input
+    /// cannot overflow the stack.
+    fn read_value(&mut self, buf: &mut Vec<u8>) -> Result<u64, JsonEntriesError> {
+        buf.clear();
+        let first = self.skip_whitespace()?.ok_or_else(|| self.eof())?;
+        let start = self.offset;
+        match first {
+            b'"' => {
+                self.consume();
+                buf.push(b'"');
+                self.read_string(buf)?;
+            }
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                loop {
+                    let byte = self.next()?;
+                    buf.push(byte);
+                    match byte {
+                        b'"' => self.read_string(buf)?,
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            b',' | b':' | b'}' | b']' => return Err(self.invalid("expected a value")),
+            // A number or literal, which ends at the next delimiter
+            _ => loop {
+                match self.peek()? {
+                    None | Some(b' ' | b'\n' | b'\t' | b'\r' | b',' | b':' | b'}' | b']') => break,
+                    Some(byte) => {
+                        self.consume();
+                        buf.push(byte);
+                    }
+                }
+            },
+        }
+        Ok(start)
+    }
+
+    /// Reads the rest of a string after its opening quote into `buf`, up to and including the
+    /// closing quote
+    fn read_string(&mut self, buf: &mut Vec<u8>) -> Result<(), JsonEntriesError> {
+        loop {
+            let byte = self.next()?;
+            buf.push(byte);
+            match byte {
+                b'"' => return Ok(()),
+                b'\\' => buf.push(self.next()?),
+                _ => {}
+            }
+        }
+    }
+}
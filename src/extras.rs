@@ -0,0 +1,157 @@
+//! Round-tripping a map without losing the entries which were skipped.
+
+use crate::{
+    seed::RawSkipped, DuplicatePolicy, LastWins, SkipReport, SkippableMap, SkippableMapSeed,
+};
+use serde::{de::DeserializeSeed, ser::SerializeMap, Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+
+/// A [`SkippableMap`] which also keeps every entry it skipped, and writes them back out again when
+/// serialized, so that a subset of the input can be edited without losing the rest.
+///
+/// Skipped entries are serialized among the entries of the map in their original positions. If
+/// the map keeps insertion order, as `IndexMap` does, then input which is deserialized and
+/// serialized again without changes keeps its order too; otherwise they are placed among the
+/// entries in whatever order the map has. Entries added to the map come after those from the input,
+/// and a skipped entry is left out if the map now has an entry with its key, or if another skipped
+/// entry later in the input has the same key, so that no key is written twice.
+/// Which of several decoded entries with the same key ends up in the map is decided by the
+/// [`DuplicatePolicy`] `P`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::SkippableMapWithExtras;
+///
+/// let json = r#"{"name":"config","retries":3,"hosts":["a","b"]}"#;
+/// let mut config: SkippableMapWithExtras<String, u64> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(config.extras_len(), 2);
+/// *config.map.0.get_mut("retries").unwrap() = 5;
+///
+/// assert_eq!(
+///     serde_json::to_string(&config).unwrap(),
+///     r#"{"name":"config","retries":5,"hosts":["a","b"]}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SkippableMapWithExtras<K, V, M = HashMap<K, V>, P = LastWins> {
+    /// Entries which decoded to `(K, V)`
+    pub map: SkippableMap<K, V, M, P>,
+    /// Entries which were skipped, in the order of the input
+    extras: Vec<Extra<K>>,
+}
+
+/// An entry which was skipped, with its key if that decoded to `K`
+#[derive(Debug, Clone)]
+struct Extra<K> {
+    key: Option<K>,
+    raw: RawSkipped,
+}
+
+impl<K, V, M, P> SkippableMapWithExtras<K, V, M, P> {
+    /// Returns the wrapped inner map, consuming self and discarding the skipped entries
+    pub fn inner(self) -> M {
+        self.map.0
+    }
+
+    /// Returns the number of entries which were skipped
+    pub fn extras_len(&self) -> usize {
+        self.extras.len()
+    }
+
+    /// Discards the skipped entries, so that only the map is serialized
+    pub fn clear_extras(&mut self) {
+        self.extras.clear();
+    }
+}
+
+impl<K, V, M, P> AsRef<M> for SkippableMapWithExtras<K, V, M, P> {
+    fn as_ref(&self) -> &M {
+        &self.map.0
+    }
+}
+
+impl<K, V, M, P> AsMut<M> for SkippableMapWithExtras<K, V, M, P> {
+    fn as_mut(&mut self) -> &mut M {
+        &mut self.map.0
+    }
+}
+
+impl<K, V, M, P> Serialize for SkippableMapWithExtras<K, V, M, P>
+where
+    K: Serialize + PartialEq,
+    V: Serialize,
+    for<'a> &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // A skipped entry whose key is in the map, or is skipped again later, would repeat the key
+        let extras: Vec<&RawSkipped> = self
+            .extras
+            .iter()
+            .enumerate()
+            .filter(|(i, extra)| match &extra.key {
+                Some(key) => {
+                    !(&self.map.0).into_iter().any(|(k, _)| k == key)
+                        && !self.extras[i + 1..]
+                            .iter()
+                            .any(|later| later.key.as_ref() == Some(key))
+                }
+                None => true,
+            })
+            .map(|(_, extra)| &extra.raw)
+            .collect();
+        let len = (&self.map.0).into_iter().count() + extras.len();
+        let mut map = serializer.serialize_map(Some(len))?;
+        let mut extras = extras.into_iter().peekable();
+        let mut written = 0;
+        for (key, value) in &self.map.0 {
+            // Skipped entries go back in their original positions, as far as the map's order allows
+            while let Some((_, extra_key, extra_value)) =
+                extras.next_if(|&&(index, _, _)| index <= written)
+            {
+                map.serialize_entry(extra_key, extra_value)?;
+                written += 1;
+            }
+            map.serialize_entry(key, value)?;
+            written += 1;
+        }
+        for (_, key, value) in extras {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V, M, P> Deserialize<'de> for SkippableMapWithExtras<K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut report = SkipReport::default();
+        let mut raw = Vec::new();
+        let map = SkippableMapSeed::new()
+            .collect_skipped(&mut report)
+            .keep_raw(&mut raw)
+            .deserialize(deserializer)?;
+        // Both have an item for every skipped entry, in order
+        let extras = report
+            .into_iter()
+            .zip(raw)
+            .map(|(skipped, raw)| Extra {
+                key: skipped.key,
+                raw,
+            })
+            .collect();
+        Ok(SkippableMapWithExtras { map, extras })
+    }
+}
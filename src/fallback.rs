@@ -0,0 +1,112 @@
+//! Value wrappers which never fail to deserialize, so that an entry of a [`SkippableMap`] whose
+//! key decodes is kept even if its value does not.
+//!
+//! [`SkippableMap`]: crate::SkippableMap
+
+use crate::{
+    content::{Content, ContentRefDeserializer},
+    SkipError,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A wrapper around `T` which deserializes to `T::default()` if the value does not decode to `T`.
+///
+/// Only a value which is well-formed falls back, as described under
+/// [Errors](crate::SkippableMap#errors).
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{OrDefault, SkippableMap};
+///
+/// let json = r#"{ "string": "b", "number": 1 }"#;
+/// let map: SkippableMap<String, OrDefault<u64>> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(map.0["number"].0, 1);
+/// assert_eq!(map.0["string"].0, 0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct OrDefault<T>(pub T);
+
+impl<T> OrDefault<T> {
+    /// Returns the wrapped value, consuming self
+    pub fn inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> AsRef<T> for OrDefault<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for OrDefault<T>
+where
+    T: Deserialize<'de> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = Content::deserialize(deserializer)?;
+        let value = T::deserialize(ContentRefDeserializer::<D::Error>::new(&content));
+        Ok(OrDefault(value.unwrap_or_default()))
+    }
+}
+
+/// A wrapper around `Result<T, SkipError>`, which holds the error if the value does not decode to
+/// `T`, rather than failing.
+///
+/// This can also be used to substitute a fallback value other than the default.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{Fallible, SkippableMap};
+///
+/// let json = r#"{ "string": "b", "number": 1 }"#;
+/// let map: SkippableMap<String, Fallible<u64>> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(map.0["number"].0, Ok(1));
+/// assert!(map.0["string"].0.is_err());
+/// assert_eq!(map.0["string"].clone().inner().unwrap_or(u64::MAX), u64::MAX);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fallible<T>(pub Result<T, SkipError>);
+
+impl<T> Fallible<T> {
+    /// Returns the wrapped result, consuming self
+    pub fn inner(self) -> Result<T, SkipError> {
+        self.0
+    }
+}
+
+impl<T> From<Fallible<T>> for Result<T, SkipError> {
+    fn from(value: Fallible<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T> AsRef<Result<T, SkipError>> for Fallible<T> {
+    fn as_ref(&self) -> &Result<T, SkipError> {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Fallible<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = Content::deserialize(deserializer)?;
+        let value = T::deserialize(ContentRefDeserializer::<D::Error>::new(&content));
+        Ok(Fallible(value.map_err(SkipError::new)))
+    }
+}
@@ -0,0 +1,106 @@
+//! Support for the code generated by `#[derive(SkippableDeserialize)]`, which is not part of the
+//! public API.
+
+use crate::{
+    content::{Content, ContentRefDeserializer},
+    visit_entries, SkipError, SkipReport, SkippedEntry,
+};
+use serde::{
+    de::{value, Error, Visitor},
+    Deserialize, Deserializer,
+};
+use std::fmt;
+
+/// The buffered values of the fields of a struct, in the order they are declared, along with the
+/// position of their entries in the input
+pub struct Fields<'de> {
+    names: &'static [&'static str],
+    values: Vec<Option<(usize, Content<'de>)>>,
+    /// The number of entries in the input
+    len: usize,
+}
+
+impl<'de> Fields<'de> {
+    /// Decodes the field declared at `field`, or records why it could not be decoded in `report`
+    /// and returns `None` so that its fallback is used instead.
+    ///
+    /// The entry is reported at its position in the input, or, if it is missing, at the number of
+    /// entries in the input, i.e. just past the end.
+    pub fn take<T>(&self, field: usize, report: &mut SkipReport<String>) -> Option<T>
+    where
+        T: Deserialize<'de>,
+    {
+        let name = self.names[field];
+        let (index, value) = match self.values[field] {
+            Some((index, ref content)) => (
+                index,
+                T::deserialize(ContentRefDeserializer::<value::Error>::new(content)),
+            ),
+            // A missing field is only an error if it is not optional
+            None => (
+                self.len,
+                T::deserialize(ContentRefDeserializer::<value::Error>::new(&Content::None))
+                    .map_err(|_| value::Error::missing_field(name)),
+            ),
+        };
+        value
+            .map_err(|error| {
+                report.0.push(SkippedEntry {
+                    index,
+                    key: Some(name.to_owned()),
+                    error: SkipError::new(error),
+                })
+            })
+            .ok()
+    }
+}
+
+/// Buffers the value of every field in `names` from a map, ignoring any other entries
+pub fn deserialize_fields<'de, D>(
+    deserializer: D,
+    name: &'static str,
+    names: &'static [&'static str],
+) -> Result<Fields<'de>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(FieldsVisitor { name, names })
+}
+
+struct FieldsVisitor {
+    name: &'static str,
+    names: &'static [&'static str],
+}
+
+impl<'de> Visitor<'de> for FieldsVisitor {
+    type Value = Fields<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "struct {}", self.name)
+    }
+
+    fn visit_map<A>(self, access: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut values: Vec<Option<(usize, Content)>> = self.names.iter().map(|_| None).collect();
+        let len = visit_entries(
+            access,
+            None,
+            |key: &String| self.names.contains(&key.as_str()),
+            |index, key, value| {
+                // Later occurrences of a field replace earlier ones
+                if let Some(field) = self.names.iter().position(|name| *name == key) {
+                    values[field] = Some((index, value));
+                }
+                Ok(())
+            },
+            |_, _, _, _| Ok(()),
+        )?;
+        Ok(Fields {
+            names: self.names,
+            values,
+            len,
+        })
+    }
+}
@@ -0,0 +1,181 @@
+//! Handling conforming entries one at a time, without collecting them into a map.
+
+use crate::{expecting_map, visit_entries};
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, marker::PhantomData, ops::ControlFlow};
+
+/// Calls `f` with each entry of a map which decodes to `(K, V)`, in the order they appear, and
+/// skips the rest as [`SkippableMap`](crate::SkippableMap) does.
+///
+/// Entries are handled as they are read, so a map far too large to collect can be processed with
+/// memory for only one entry at a time. This means that an [error](crate::SkippableMap#errors) in
+/// the input may be returned after `f` has been called with the entries before it. The error is
+/// boxed so that only `K` and `V` need to be named: it can be downcast to the deserializer's own.
+///
+/// If `f` returns [`ControlFlow::Break`], no more of the input is read, and `ControlFlow::Break` is
+/// returned. This can be used to stop once an entry is found, or to fail with an error of the
+/// caller's own, without reading the rest of the map.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::for_each_conforming;
+/// use std::ops::ControlFlow;
+///
+/// let json = r#"{ "a": 1, "b": "x", "c": 2, "d": 3 }"#;
+/// let mut total = 0;
+/// let flow = for_each_conforming::<String, u64>(
+///     &mut serde_json::Deserializer::from_str(json),
+///     |key, n| {
+///         total += n;
+///         if key == "c" {
+///             ControlFlow::Break(())
+///         } else {
+///             ControlFlow::Continue(())
+///         }
+///     },
+/// )
+/// .unwrap();
+///
+/// assert_eq!(total, 3);
+/// assert!(flow.is_break());
+/// ```
+pub fn for_each_conforming<'de, K, V>(
+    deserializer: impl Deserializer<'de, Error: Send + Sync + 'static>,
+    f: impl FnMut(K, V) -> ControlFlow<()>,
+) -> Result<ControlFlow<()>, Box<dyn std::error::Error + Send + Sync>>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    Ok(for_each(deserializer, f)?)
+}
+
+/// Does the work of [`for_each_conforming`], returning the deserializer's error as it is
+fn for_each<'de, K, V, D, F>(deserializer: D, f: F) -> Result<ControlFlow<()>, D::Error>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+    F: FnMut(K, V) -> ControlFlow<()>,
+{
+    let mut stopped = false;
+    let result = deserializer.deserialize_map(ForEachVisitor {
+        f,
+        stopped: &mut stopped,
+        marker: PhantomData,
+    });
+    // Stopping early unwinds through the deserializer as an error, which is not the caller's
+    if stopped {
+        Ok(ControlFlow::Break(()))
+    } else {
+        result.map(|()| ControlFlow::Continue(()))
+    }
+}
+
+/// A [`DeserializeSeed`] which adds each entry of a map that decodes to `(K, V)` to a sink, such as
+/// an existing map or a `Vec`, and skips the rest as [`SkippableMap`](crate::SkippableMap) does.
+///
+/// This behaves as [`for_each_conforming`] with a callback which extends `sink`, so entries are
+/// added to it as they are read, except that errors are the deserializer's own.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde_json;
+/// use skippable_map::ExtendSeed;
+///
+/// let mut pairs = vec![(String::from("z"), 0)];
+/// let json = r#"{ "a": 1, "b": "x", "c": 2 }"#;
+/// ExtendSeed::new(&mut pairs)
+///     .deserialize(&mut serde_json::Deserializer::from_str(json))
+///     .unwrap();
+///
+/// assert_eq!(pairs, [("z".into(), 0), ("a".into(), 1), ("c".into(), 2)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct ExtendSeed<'a, S, K, V> {
+    sink: &'a mut S,
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, S, K, V> ExtendSeed<'a, S, K, V>
+where
+    S: Extend<(K, V)>,
+{
+    /// Creates a seed which adds entries to `sink`
+    pub fn new(sink: &'a mut S) -> Self {
+        Self {
+            sink,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, S, K, V> DeserializeSeed<'de> for ExtendSeed<'a, S, K, V>
+where
+    S: Extend<(K, V)>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let sink = self.sink;
+        for_each(deserializer, |key, value| {
+            sink.extend(std::iter::once((key, value)));
+            ControlFlow::Continue(())
+        })
+        // The callback never breaks
+        .map(|_| ())
+    }
+}
+
+#[allow(clippy::type_complexity)]
+struct ForEachVisitor<'a, F, K, V> {
+    f: F,
+    /// Whether `f` broke
+    stopped: &'a mut bool,
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, 'de, F, K, V> Visitor<'de> for ForEachVisitor<'a, F, K, V>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    F: FnMut(K, V) -> ControlFlow<()>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        expecting_map::<K, V>(formatter)
+    }
+
+    fn visit_map<A>(mut self, access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        visit_entries(
+            access,
+            None,
+            |_| true,
+            |_, key, value| match (self.f)(key, value) {
+                ControlFlow::Continue(()) => Ok(()),
+                ControlFlow::Break(()) => {
+                    *self.stopped = true;
+                    Err(A::Error::custom("stopped early"))
+                }
+            },
+            |_, _, _, _| Ok(()),
+        )?;
+        Ok(())
+    }
+}
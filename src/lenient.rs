@@ -0,0 +1,86 @@
+//! Functions for deserializing plain map fields with the skipping behaviour of [`SkippableMap`],
+//! without the wrapper appearing in the type of the field.
+//!
+//! These can be used with `#[serde(deserialize_with = "skippable_map::lenient::deserialize")]`, or
+//! with `#[serde(with = "skippable_map::lenient")]` which also serializes the map as normal. For
+//! fields of type `Option<M>`, use the functions in [`option`] in the same way.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use serde_json;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Config {
+//!     #[serde(with = "skippable_map::lenient")]
+//!     limits: HashMap<String, u64>,
+//!     #[serde(default, with = "skippable_map::lenient::option")]
+//!     overrides: Option<HashMap<String, u64>>,
+//! }
+//!
+//! let json = r#"{
+//!     "limits": { "string": "b", "number": 1 },
+//!     "overrides": { "negative_number": -44, "other_number": 2 }
+//! }"#;
+//! let config: Config = serde_json::from_str(json).unwrap();
+//!
+//! assert_eq!(config.limits, HashMap::from([(String::from("number"), 1)]));
+//! assert_eq!(
+//!     config.overrides,
+//!     Some(HashMap::from([(String::from("other_number"), 2)]))
+//! );
+//! ```
+
+use crate::{MapInsert, SkippableMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Deserializes a map, skipping any entry which does not decode to `(K, V)`
+pub fn deserialize<'de, D, K, V, M>(deserializer: D) -> Result<M, D::Error>
+where
+    D: Deserializer<'de>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    M: MapInsert<K, V>,
+{
+    SkippableMap::<K, V, M>::deserialize(deserializer).map(SkippableMap::inner)
+}
+
+/// Serializes a map as normal
+pub fn serialize<S, M>(map: &M, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    M: Serialize,
+{
+    map.serialize(serializer)
+}
+
+/// As the parent module, but for fields of type `Option<M>`.
+///
+/// Combine with `#[serde(default)]` to allow the field to be missing entirely.
+pub mod option {
+    use crate::{MapInsert, SkippableMap};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes an optional map, skipping any entry which does not decode to `(K, V)`
+    pub fn deserialize<'de, D, K, V, M>(deserializer: D) -> Result<Option<M>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        M: MapInsert<K, V>,
+    {
+        Option::<SkippableMap<K, V, M>>::deserialize(deserializer)
+            .map(|map| map.map(SkippableMap::inner))
+    }
+
+    /// Serializes an optional map as normal
+    pub fn serialize<S, M>(map: &Option<M>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        M: Serialize,
+    {
+        map.serialize(serializer)
+    }
+}
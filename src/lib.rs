@@ -26,10 +26,127 @@
 //! // Consumes just_numbers to produce inner HashMap
 //! assert_eq!(just_numbers.inner(), hm);
 //! ```
+//!
+//! To find out which entries were skipped and why, deserialize a [`SkippableMapWithReport`]
+//! instead, and to configure skipping at runtime use [`SkippableMapSeed`]. Sequences can be
+//! deserialized in the same way with [`SkippableVec`]. To keep entries
+//! whose key decodes but whose value does not, wrap the value in [`OrDefault`] or [`Fallible`],
+//! and to accept values of several types, use [`OneOf`]. Numbers and booleans written as strings,
+//! or integers written as floats, can be accepted with [`Coerce`].
+//! To edit a map and serialize it again without losing the skipped entries, use
+//! [`SkippableMapWithExtras`], and to accept input which is not a map at all, use
+//! [`AlwaysSkippableMap`].
+//!
+//! To handle entries one at a time as they are read, rather than collecting them into a map, use
+//! [`for_each_conforming`], or [`ExtendSeed`] to add them to an existing collection.
+//!
+//! To split a heterogeneous map by type in a single pass, deserialize a [`Partitioned`] tuple of
+//! maps, each entry going to the first one it conforms to.
+//!
+//! To skip bad elements of nested containers, rather than the whole entry which contains them,
+//! wrap the value in [`Nested`].
+//!
+//! To keep the wrapper out of the type of a struct field, use the functions in [`lenient`] with
+//! `#[serde(with = "skippable_map::lenient")]`.
+//!
+//! # Features
+//!
+//! Entries are collected into a [`HashMap`] by default. Other maps can be used by enabling the
+//! corresponding feature:
+//!
+//! - `btreemap`: [`BTreeMap`](std::collections::BTreeMap)
+//! - `indexmap`: [`IndexMap`](https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html)
+//!
+//! The `serde_with` feature provides `SkipInvalid`, an adapter for use with
+//! [`serde_with::serde_as`](https://docs.rs/serde_with/latest/serde_with/attr.serde_as.html).
+//!
+//! The `derive` feature provides `#[derive(SkippableDeserialize)]` for structs, where a field
+//! whose value does not conform falls back to a default rather than failing the whole struct, and
+//! `#[derive(SkippablePartitions)]` for structs of maps to be filled as with `Partitioned`.
+//!
+//! The `json` feature provides `SkippableMapWithRest`, which keeps the skipped entries as
+//! [`serde_json::Value`](https://docs.rs/serde_json/latest/serde_json/enum.Value.html)s, and
+//! `JsonEntries`, an iterator over the conforming entries of a JSON object read lazily from an
+//! [`io::Read`](std::io::Read).
+//!
+//! The `json`, `msgpack`, `cbor`, `bincode` and `postcard` features implement [`Recoverable`] for
+//! the errors of the corresponding format, so that [`SkippableMapSeed::deserialize_recoverable`] can
+//! read maps without buffering them, skipping only the entries the format can continue after.
 
-use serde::{de::Visitor, Deserialize, Serialize};
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+use content::{Content, ContentRefDeserializer, KeyRefDeserializer};
+use pairs::visit_pairs;
+use seed::Options;
+use serde::{
+    de::{Error, IgnoredAny, Unexpected, Visitor},
+    Deserialize, Serialize,
+};
 use std::{collections::HashMap, marker::PhantomData};
 
+mod always;
+mod coerce;
+mod content;
+mod direct;
+#[cfg(feature = "json")]
+mod entries;
+mod extras;
+mod fallback;
+#[cfg(feature = "derive")]
+mod fields;
+mod for_each;
+pub mod lenient;
+mod map;
+mod nested;
+mod one_of;
+mod pairs;
+mod partition;
+mod policy;
+mod recoverable;
+mod report;
+#[cfg(feature = "json")]
+mod rest;
+mod seed;
+mod size_hint;
+#[cfg(feature = "serde_with")]
+mod skip_invalid;
+mod vec;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "derive")]
+    pub use crate::fields::{deserialize_fields, Fields};
+    #[cfg(feature = "derive")]
+    pub use crate::partition::deserialize_partitions;
+    pub use crate::partition::PartitionEntry;
+    pub use serde::{Deserialize, Deserializer};
+}
+
+pub use always::AlwaysSkippableMap;
+pub use coerce::Coerce;
+#[cfg(feature = "json")]
+pub use entries::{JsonEntries, JsonEntriesError};
+pub use extras::SkippableMapWithExtras;
+pub use fallback::{Fallible, OrDefault};
+pub use for_each::{for_each_conforming, ExtendSeed};
+pub use map::MapInsert;
+pub use nested::{Nested, NestedDeserialize};
+pub use one_of::OneOf;
+pub use pairs::PairShape;
+pub use partition::{Partition, Partitioned};
+pub use policy::{CollectAll, DenyDuplicates, DuplicateKey, DuplicatePolicy, FirstWins, LastWins};
+pub use recoverable::Recoverable;
+pub use report::{SkipError, SkipReport, SkippableMapWithReport, SkippedEntry};
+#[cfg(feature = "json")]
+pub use rest::SkippableMapWithRest;
+pub use seed::SkippableMapSeed;
+#[cfg(feature = "serde_with")]
+pub use skip_invalid::SkipInvalid;
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use skippable_map_derive::{SkippableDeserialize, SkippablePartitions};
+pub use vec::SkippableVec;
+
 /// The central struct of the library: this is a wrapper around [`HashMap`] with a custom
 /// implementation of [`Deserialize`].
 /// The implementation goes through the data to be deserialized, and skips any field which does not
@@ -38,13 +155,33 @@ use std::{collections::HashMap, marker::PhantomData};
 /// This means that we can pass a data structure with additional components not in this format
 /// which will be skipped.
 ///
+/// Entries are collected into a [`HashMap`] by default, but any map implementing [`MapInsert`] can
+/// be used in its place via `M`, e.g. `SkippableMap<K, V, BTreeMap<K, V>>`, or
+/// `SkippableMap<K, V, HashMap<K, V, S>>` for a custom [`BuildHasher`](std::hash::BuildHasher).
+/// If a key appears more than once, the last value is kept: this can be changed by choosing
+/// another [`DuplicatePolicy`] for `P`.
+///
+/// Input which is a sequence of key/value pairs rather than a map can be accepted by choosing the
+/// [`PairShape`]s of pair with [`SkippableMapSeed::pair_shapes`].
+///
+/// Keys and values are buffered before being decoded, so the data format must be self-describing.
+/// Formats which are not, such as bincode and postcard, are read with
+/// [`SkippableMapSeed::deserialize_recoverable`].
+/// Keys which are strings, as in JSON and TOML, may be decoded as numbers or booleans.
+///
+/// # Errors
+///
+/// Only entries which are well-formed but of the wrong type are skipped: if the input itself is
+/// broken (e.g. truncated, a syntax error, or an I/O error while reading) the error is returned.
+/// The other types in this crate skip or fall back on the same terms.
+///
 /// # Examples
 ///
 /// ```rust
 /// use serde_json;
 /// use skippable_map::SkippableMap;
 /// use std::collections::HashMap;
-
+///
 /// let json = r#"{ "string": "b", "number": 1, "other_number": 2, "negative_number": -44}"#;
 /// // SkippableMap<String, u64> will skip the (String, String) entry, and the negative number
 /// let just_numbers: SkippableMap<String, u64> = serde_json::from_str(json).unwrap();
@@ -57,83 +194,201 @@ use std::{collections::HashMap, marker::PhantomData};
 /// ```
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(transparent)]
-pub struct SkippableMap<K, V>(pub HashMap<K, V>);
+#[allow(clippy::type_complexity)]
+pub struct SkippableMap<K, V, M = HashMap<K, V>, P = LastWins>(
+    pub M,
+    #[serde(skip)] PhantomData<fn() -> (K, V, P)>,
+);
+
+impl<K, V, M, P> SkippableMap<K, V, M, P> {
+    /// Wraps an existing map
+    pub fn new(map: M) -> Self {
+        Self(map, PhantomData)
+    }
 
-impl<K, V> SkippableMap<K, V> {
-    /// Returns the wrapped inner [`HashMap`], consuming self
-    pub fn inner(self) -> HashMap<K, V> {
+    /// Returns the wrapped inner map, consuming self
+    pub fn inner(self) -> M {
         self.0
     }
 }
 
-struct SkippableMapVisitor<K, V> {
-    marker: PhantomData<fn() -> SkippableMap<K, V>>,
+#[allow(clippy::type_complexity)]
+struct SkippableMapVisitor<'a, K, V, M, P> {
+    options: Options<'a, K>,
+    marker: PhantomData<fn() -> SkippableMap<K, V, M, P>>,
 }
 
-impl<K, V> SkippableMapVisitor<K, V> {
+impl<'a, K, V, M, P> SkippableMapVisitor<'a, K, V, M, P> {
     fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    fn with_options(options: Options<'a, K>) -> Self {
         Self {
+            options,
             marker: PhantomData,
         }
     }
 }
 
-impl<'de, K, V> Visitor<'de> for SkippableMapVisitor<K, V>
+impl<'a, 'de, K, V, M, P> Visitor<'de> for SkippableMapVisitor<'a, K, V, M, P>
 where
-    K: Deserialize<'de> + std::hash::Hash + std::cmp::Eq,
+    K: Deserialize<'de>,
     V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
 {
-    type Value = SkippableMap<K, V>;
+    type Value = SkippableMap<K, V, M, P>;
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            formatter,
-            "a data structure which contains some mappings from {} to {}",
-            std::any::type_name::<K>(),
-            std::any::type_name::<V>(),
-        )
+        expecting_map::<K, V>(formatter)
     }
 
-    fn visit_map<A>(self, mut access: A) -> std::result::Result<Self::Value, A::Error>
+    fn visit_map<A>(self, access: A) -> std::result::Result<Self::Value, A::Error>
     where
         A: serde::de::MapAccess<'de>,
     {
-        let mut map = HashMap::with_capacity(access.size_hint().unwrap_or(0));
-
-        // Skips any entries which don't decode to map from K to V
-        loop {
-            let r = access.next_entry();
-            match r {
-                // Success in decoding (insert)
-                Ok(Some((key, value))) => {
-                    map.insert(key, value);
-                }
-                // Error in decoding (skip)
-                Err(_) => {}
-                // End of data structure (end)
-                Ok(None) => {
-                    return Ok(SkippableMap(map));
-                }
-            };
+        let Options {
+            key_filter,
+            max_entries,
+            mut skips,
+            ..
+        } = self.options;
+        let mut map = P::new_map(size_hint::cautious::<(K, V)>(access.size_hint()));
+        visit_entries(
+            access,
+            max_entries,
+            |key| key_filter.as_ref().is_none_or(|filter| filter(key)),
+            |index, key, value| P::insert(&mut map, key, value).map_err(|error| error.at(index)),
+            |index, key, error, raw| {
+                skips.record(index, key, error)?;
+                skips.record_raw(index, raw);
+                Ok(())
+            },
+        )?;
+        Ok(SkippableMap::new(map))
+    }
+
+    fn visit_seq<A>(self, access: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        if self.options.pair_shapes.is_empty() {
+            return Err(Error::invalid_type(Unexpected::Seq, &self));
+        }
+        let Options {
+            key_filter,
+            max_entries,
+            pair_shapes,
+            mut skips,
+        } = self.options;
+        let mut map = P::new_map(size_hint::cautious::<(K, V)>(access.size_hint()));
+        visit_pairs(
+            access,
+            pair_shapes,
+            max_entries,
+            |key| key_filter.as_ref().is_none_or(|filter| filter(key)),
+            |index, key, value| P::insert(&mut map, key, value).map_err(|error| error.at(index)),
+            |index, key, error| skips.record(index, key, error),
+        )?;
+        Ok(SkippableMap::new(map))
+    }
+}
+
+/// Describes the map expected by each visitor which skips entries that do not decode to `(K, V)`
+fn expecting_map<K, V>(formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(
+        formatter,
+        "a data structure which contains some mappings from {} to {}",
+        std::any::type_name::<K>(),
+        std::any::type_name::<V>(),
+    )
+}
+
+/// The buffered key and value of an entry which was skipped
+type RawEntry<'a, 'de> = (&'a Content<'de>, &'a Content<'de>);
+
+/// Reads every entry of `access`, passing those which decode to `(K, V)` to `insert` and those
+/// which do not to `skip`, along with their position, the key if it decoded, and the buffered
+/// [`RawEntry`] so that it can be kept in another form. Entries whose key
+/// is rejected by `keep` are passed to neither, and their values are not decoded at all. Reading
+/// more than `max_entries` entries of any kind is an error. Returns the number of entries read.
+///
+/// Keys and values are buffered first, so that an error in the input itself (a syntax error, EOF,
+/// I/O, ...) can be told apart from an entry which is well-formed but does not decode to `(K, V)`:
+/// the former is returned rather than retried, as the input cannot make progress past it, while
+/// the latter always has its whole entry consumed before moving on to the next one.
+fn visit_entries<'de, A, K, V>(
+    mut access: A,
+    max_entries: Option<usize>,
+    keep: impl Fn(&K) -> bool,
+    mut insert: impl FnMut(usize, K, V) -> std::result::Result<(), A::Error>,
+    mut skip: impl FnMut(
+        usize,
+        Option<K>,
+        A::Error,
+        RawEntry<'_, 'de>,
+    ) -> std::result::Result<(), A::Error>,
+) -> std::result::Result<usize, A::Error>
+where
+    A: serde::de::MapAccess<'de>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    let mut len = 0;
+    for index in 0.. {
+        let raw_key: Content = match access.next_key()? {
+            Some(raw_key) => raw_key,
+            // End of data structure (end)
+            None => break,
+        };
+        len = index + 1;
+        if let Some(max) = max_entries.filter(|&max| index >= max) {
+            return Err(Error::custom(format_args!(
+                "map has more than {max} entries"
+            )));
+        }
+        let key = match K::deserialize(KeyRefDeserializer::<A::Error>::new(&raw_key)) {
+            // Success in decoding key
+            Ok(key) => key,
+            // Error in decoding key (skip value, so the next entry starts in the right place)
+            Err(e) => {
+                let raw_value: Content = access.next_value()?;
+                skip(index, None, e, (&raw_key, &raw_value))?;
+                continue;
+            }
+        };
+        // Key rejected (skip value without decoding it)
+        if !keep(&key) {
+            access.next_value::<IgnoredAny>()?;
+            continue;
+        }
+        let raw_value: Content = access.next_value()?;
+        match V::deserialize(ContentRefDeserializer::<A::Error>::new(&raw_value)) {
+            // Success in decoding value (insert)
+            Ok(value) => insert(index, key, value)?,
+            // Error in decoding value (skip)
+            Err(e) => skip(index, Some(key), e, (&raw_key, &raw_value))?,
         }
     }
+    Ok(len)
 }
 
-impl<K, V> From<SkippableMap<K, V>> for HashMap<K, V> {
-    fn from(value: SkippableMap<K, V>) -> Self {
-        value.0
+impl<K, V, M, P> From<M> for SkippableMap<K, V, M, P> {
+    fn from(value: M) -> Self {
+        SkippableMap::new(value)
     }
 }
 
-impl<K, V> AsRef<HashMap<K, V>> for SkippableMap<K, V> {
-    fn as_ref(&self) -> &HashMap<K, V> {
+impl<K, V, M, P> AsRef<M> for SkippableMap<K, V, M, P> {
+    fn as_ref(&self) -> &M {
         &self.0
     }
 }
 
-impl<'de, K, V> Deserialize<'de> for SkippableMap<K, V>
+impl<'de, K, V, M, P> Deserialize<'de> for SkippableMap<K, V, M, P>
 where
-    K: Deserialize<'de> + std::hash::Hash + std::cmp::Eq,
+    K: Deserialize<'de>,
     V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
 {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
@@ -0,0 +1,168 @@
+use crate::SkippableMap;
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// A map which [`SkippableMap`] can collect the entries it keeps into.
+///
+/// This is implemented for [`HashMap`], and behind their respective features for
+/// [`BTreeMap`](std::collections::BTreeMap) (`btreemap`) and
+/// [`IndexMap`](https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html) (`indexmap`).
+pub trait MapInsert<K, V> {
+    /// Creates an empty map, with space for at least `capacity` entries if the map supports it
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Inserts an entry, replacing the value of any existing entry with the same key
+    fn insert(&mut self, key: K, value: V);
+
+    /// Returns a mutable reference to the value of the entry with the given key, if there is one
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+}
+
+/// Any [`BuildHasher`] can be used, so long as it can be created with [`Default`].
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::SkippableMap;
+/// use std::{
+///     collections::{hash_map::DefaultHasher, HashMap},
+///     hash::BuildHasherDefault,
+/// };
+///
+/// type Hasher = BuildHasherDefault<DefaultHasher>;
+///
+/// let json = r#"{ "string": "b", "number": 1 }"#;
+/// let just_numbers: SkippableMap<String, u64, HashMap<String, u64, Hasher>> =
+///     serde_json::from_str(json).unwrap();
+/// let hm: HashMap<String, u64, Hasher> = just_numbers.into();
+///
+/// assert_eq!(hm.get("number"), Some(&1));
+/// ```
+impl<K, V, S> MapInsert<K, V> for HashMap<K, V, S>
+where
+    K: std::hash::Hash + std::cmp::Eq,
+    S: BuildHasher + Default,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        HashMap::with_capacity_and_hasher(capacity, S::default())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        HashMap::get_mut(self, key)
+    }
+}
+
+/// Any [`DuplicatePolicy`](crate::DuplicatePolicy) can be used, including those which collect
+/// values of another type than `V`.
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{CollectAll, SkippableMap};
+/// use std::collections::HashMap;
+///
+/// let json = r#"{ "a": 1, "a": 2 }"#;
+/// let all: SkippableMap<String, u64, HashMap<String, Vec<u64>>, CollectAll> =
+///     serde_json::from_str(json).unwrap();
+/// let hm = HashMap::from(all);
+///
+/// assert_eq!(hm["a"], [1, 2]);
+/// ```
+impl<K, V, W, S, P> From<SkippableMap<K, W, HashMap<K, V, S>, P>> for HashMap<K, V, S> {
+    fn from(value: SkippableMap<K, W, HashMap<K, V, S>, P>) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "btreemap")]
+mod btreemap {
+    use super::MapInsert;
+    use crate::SkippableMap;
+    use std::collections::BTreeMap;
+
+    /// Collects entries in key order, for deterministic output.
+    ///
+    /// ```rust
+    /// use serde_json;
+    /// use skippable_map::SkippableMap;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let json = r#"{ "z": 1, "string": "b", "a": 2 }"#;
+    /// let just_numbers: SkippableMap<String, u64, BTreeMap<String, u64>> =
+    ///     serde_json::from_str(json).unwrap();
+    ///
+    /// assert_eq!(serde_json::to_string(&just_numbers).unwrap(), r#"{"a":2,"z":1}"#);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "btreemap")))]
+    impl<K, V> MapInsert<K, V> for BTreeMap<K, V>
+    where
+        K: Ord,
+    {
+        fn with_capacity(_capacity: usize) -> Self {
+            BTreeMap::new()
+        }
+
+        fn insert(&mut self, key: K, value: V) {
+            BTreeMap::insert(self, key, value);
+        }
+
+        fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+            BTreeMap::get_mut(self, key)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "btreemap")))]
+    impl<K, V, W, P> From<SkippableMap<K, W, BTreeMap<K, V>, P>> for BTreeMap<K, V> {
+        fn from(value: SkippableMap<K, W, BTreeMap<K, V>, P>) -> Self {
+            value.0
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+mod indexmap {
+    use super::MapInsert;
+    use crate::SkippableMap;
+    use indexmap::IndexMap;
+    use std::hash::BuildHasher;
+
+    /// Collects entries in the order they appear in the input.
+    ///
+    /// ```rust
+    /// use indexmap::IndexMap;
+    /// use serde_json;
+    /// use skippable_map::SkippableMap;
+    ///
+    /// let json = r#"{ "z": 1, "string": "b", "a": 2 }"#;
+    /// let just_numbers: SkippableMap<String, u64, IndexMap<String, u64>> =
+    ///     serde_json::from_str(json).unwrap();
+    ///
+    /// assert_eq!(serde_json::to_string(&just_numbers).unwrap(), r#"{"z":1,"a":2}"#);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+    impl<K, V, S> MapInsert<K, V> for IndexMap<K, V, S>
+    where
+        K: std::hash::Hash + std::cmp::Eq,
+        S: BuildHasher + Default,
+    {
+        fn with_capacity(capacity: usize) -> Self {
+            IndexMap::with_capacity_and_hasher(capacity, S::default())
+        }
+
+        fn insert(&mut self, key: K, value: V) {
+            IndexMap::insert(self, key, value);
+        }
+
+        fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+            IndexMap::get_mut(self, key)
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+    impl<K, V, W, S, P> From<SkippableMap<K, W, IndexMap<K, V, S>, P>> for IndexMap<K, V, S> {
+        fn from(value: SkippableMap<K, W, IndexMap<K, V, S>, P>) -> Self {
+            value.0
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! Skipping applied recursively to nested containers.
+
+use crate::{
+    content::{Content, ContentRefDeserializer, KeyRefDeserializer},
+    expecting_map,
+};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
+
+/// Types which can be deserialized while skipping whatever does not conform within them, rather
+/// than failing as a whole.
+///
+/// Sequences and maps skip any element or entry which does not decode, and options become `None`
+/// if their value does not decode. As the elements are themselves deserialized leniently, a bad
+/// value only removes the smallest sequence element, map entry or option which encloses it.
+/// Leaves, such as numbers and strings, have nothing to skip and so fail as normal.
+///
+/// This is implemented for the standard library's scalar types and [`String`], and can be
+/// implemented for other types which should be treated as leaves with
+/// [`nested_leaf!`](crate::nested_leaf). Use it through [`Nested`].
+pub trait NestedDeserialize<'de>: Sized {
+    /// Deserializes a value, skipping whatever does not conform within it
+    fn deserialize_nested<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+
+    /// Decodes a value which has already been buffered, skipping whatever does not conform within
+    /// it. Containers override this to decode their elements from the same buffer, rather than
+    /// buffering each of them again at every level of nesting.
+    #[doc(hidden)]
+    fn deserialize_nested_buffered<E>(buffered: Buffered<'_, 'de>) -> Result<Self, E>
+    where
+        E: de::Error,
+    {
+        Self::deserialize_nested(ContentRefDeserializer::<E>::new(buffered.0))
+    }
+}
+
+/// A value which has already been read into memory
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct Buffered<'a, 'de>(&'a Content<'de>);
+
+/// A wrapper around `T` whose implementation of [`Deserialize`] applies the skipping of
+/// [`NestedDeserialize`] throughout `T`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_json;
+/// use skippable_map::{nested_leaf, Nested, SkippableMap};
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+/// }
+///
+/// nested_leaf!(Point);
+///
+/// let json = r#"{
+///     "line": [{ "x": 1, "y": 2 }, { "x": -1, "y": 0 }, { "x": 3, "y": 4 }],
+///     "named": { "origin": { "x": 0, "y": 0 }, "bad": { "x": "0" } },
+///     "missing": null,
+///     "wrong": "not a list"
+/// }"#;
+/// let map: SkippableMap<String, Nested<Option<Vec<Point>>>> =
+///     serde_json::from_str(json).unwrap();
+///
+/// // Only the bad point is removed, rather than the whole line
+/// assert_eq!(
+///     map.0["line"].0,
+///     Some(vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }])
+/// );
+/// assert_eq!(map.0["missing"].0, None);
+/// assert_eq!(map.0["wrong"].0, None);
+///
+/// let nested: Nested<HashMap<String, HashMap<String, Point>>> =
+///     serde_json::from_str(json).unwrap();
+/// assert_eq!(nested.0["named"].len(), 1);
+/// assert!(!nested.0.contains_key("line"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Nested<T>(pub T);
+
+impl<T> Nested<T> {
+    /// Returns the wrapped value, consuming self
+    pub fn inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> AsRef<T> for Nested<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Nested<T>
+where
+    T: NestedDeserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_nested(deserializer).map(Nested)
+    }
+}
+
+/// Implements [`NestedDeserialize`] for types which are deserialized as normal, i.e. whose
+/// contents are not skipped.
+///
+/// The types must implement [`Deserialize`] for all lifetimes.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use skippable_map::nested_leaf;
+///
+/// #[derive(Deserialize)]
+/// struct Point {
+///     x: u64,
+///     y: u64,
+/// }
+///
+/// nested_leaf!(Point);
+/// ```
+#[macro_export]
+macro_rules! nested_leaf {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<'de> $crate::NestedDeserialize<'de> for $ty {
+                fn deserialize_nested<D>(
+                    deserializer: D,
+                ) -> ::std::result::Result<Self, <D as $crate::__private::Deserializer<'de>>::Error>
+                where
+                    D: $crate::__private::Deserializer<'de>,
+                {
+                    <$ty as $crate::__private::Deserialize<'de>>::deserialize(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+nested_leaf!(bool, char, String, ());
+nested_leaf!(u8, u16, u32, u64, u128, usize);
+nested_leaf!(i8, i16, i32, i64, i128, isize);
+nested_leaf!(f32, f64);
+
+impl<'de, T> NestedDeserialize<'de> for Option<T>
+where
+    T: NestedDeserialize<'de>,
+{
+    fn deserialize_nested<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = Content::deserialize(deserializer)?;
+        Self::deserialize_nested_buffered(Buffered(&content))
+    }
+
+    fn deserialize_nested_buffered<E>(buffered: Buffered<'_, 'de>) -> Result<Self, E>
+    where
+        E: de::Error,
+    {
+        let content = match buffered.0 {
+            Content::None | Content::Unit => return Ok(None),
+            Content::Some(content) => content,
+            content => content,
+        };
+        Ok(T::deserialize_nested_buffered::<E>(Buffered(content)).ok())
+    }
+}
+
+impl<'de, T> NestedDeserialize<'de> for Box<T>
+where
+    T: NestedDeserialize<'de>,
+{
+    fn deserialize_nested<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize_nested(deserializer).map(Box::new)
+    }
+
+    fn deserialize_nested_buffered<E>(buffered: Buffered<'_, 'de>) -> Result<Self, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize_nested_buffered(buffered).map(Box::new)
+    }
+}
+
+impl<'de, T> NestedDeserialize<'de> for Vec<T>
+where
+    T: NestedDeserialize<'de>,
+{
+    fn deserialize_nested<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(NestedSeqVisitor::new())
+    }
+
+    fn deserialize_nested_buffered<E>(buffered: Buffered<'_, 'de>) -> Result<Self, E>
+    where
+        E: de::Error,
+    {
+        NestedSeqVisitor::new().visit_buffered(buffered)
+    }
+}
+
+macro_rules! nested_map {
+    ($($map:ident)::+ <K, V $(, $s:ident)?> where K: $($bound:path),+) => {
+        impl<'de, K, V $(, $s)?> NestedDeserialize<'de> for $($map)::+<K, V $(, $s)?>
+        where
+            K: Deserialize<'de> $(+ $bound)+,
+            V: NestedDeserialize<'de>,
+            $($s: BuildHasher + Default,)?
+        {
+            fn deserialize_nested<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_map(NestedMapVisitor::new())
+            }
+
+            fn deserialize_nested_buffered<E>(buffered: Buffered<'_, 'de>) -> Result<Self, E>
+            where
+                E: de::Error,
+            {
+                NestedMapVisitor::new().visit_buffered(buffered)
+            }
+        }
+    };
+}
+
+nested_map!(HashMap<K, V, S> where K: Hash, Eq);
+nested_map!(BTreeMap<K, V> where K: Ord);
+#[cfg(feature = "indexmap")]
+nested_map!(indexmap::IndexMap<K, V, S> where K: Hash, Eq);
+
+struct NestedSeqVisitor<C, T> {
+    marker: PhantomData<fn() -> (C, T)>,
+}
+
+impl<'de, C, T> NestedSeqVisitor<C, T>
+where
+    C: Default + Extend<T>,
+    T: NestedDeserialize<'de>,
+{
+    fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+
+    /// Decodes the elements of a buffered sequence, skipping those which do not conform
+    fn visit_buffered<E>(self, buffered: Buffered<'_, 'de>) -> Result<C, E>
+    where
+        E: de::Error,
+    {
+        let Content::Seq(elements) = buffered.0 else {
+            return Err(E::invalid_type(buffered.0.unexpected(), &self));
+        };
+        let mut collection = C::default();
+        for element in elements {
+            if let Ok(element) = T::deserialize_nested_buffered::<E>(Buffered(element)) {
+                collection.extend(Some(element));
+            }
+        }
+        Ok(collection)
+    }
+}
+
+impl<'de, C, T> Visitor<'de> for NestedSeqVisitor<C, T>
+where
+    C: Default + Extend<T>,
+    T: NestedDeserialize<'de>,
+{
+    type Value = C;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a sequence which contains some elements of {}",
+            std::any::type_name::<T>(),
+        )
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // Each element is buffered once here, and its own elements are then decoded from that
+        // buffer rather than being read into another
+        let mut collection = C::default();
+        while let Some(element) = access.next_element::<Content>()? {
+            if let Ok(element) = T::deserialize_nested_buffered::<A::Error>(Buffered(&element)) {
+                collection.extend(Some(element));
+            }
+        }
+        Ok(collection)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+struct NestedMapVisitor<C, K, V> {
+    marker: PhantomData<fn() -> (C, K, V)>,
+}
+
+impl<'de, C, K, V> NestedMapVisitor<C, K, V>
+where
+    C: Default + Extend<(K, V)>,
+    K: Deserialize<'de>,
+    V: NestedDeserialize<'de>,
+{
+    fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+
+    /// Decodes the entries of a buffered map, skipping those which do not conform
+    fn visit_buffered<E>(self, buffered: Buffered<'_, 'de>) -> Result<C, E>
+    where
+        E: de::Error,
+    {
+        let Content::Map(entries) = buffered.0 else {
+            return Err(E::invalid_type(buffered.0.unexpected(), &self));
+        };
+        let mut collection = C::default();
+        for (key, value) in entries {
+            if let Some(entry) = decode_entry::<K, V, E>(key, value) {
+                collection.extend(Some(entry));
+            }
+        }
+        Ok(collection)
+    }
+}
+
+impl<'de, C, K, V> Visitor<'de> for NestedMapVisitor<C, K, V>
+where
+    C: Default + Extend<(K, V)>,
+    K: Deserialize<'de>,
+    V: NestedDeserialize<'de>,
+{
+    type Value = C;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        expecting_map::<K, V>(formatter)
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut collection = C::default();
+        while let Some((key, value)) = access.next_entry::<Content, Content>()? {
+            if let Some(entry) = decode_entry::<K, V, A::Error>(&key, &value) {
+                collection.extend(Some(entry));
+            }
+        }
+        Ok(collection)
+    }
+}
+
+/// Decodes a buffered entry, or returns `None` if it does not conform
+fn decode_entry<'de, K, V, E>(key: &Content<'de>, value: &Content<'de>) -> Option<(K, V)>
+where
+    K: Deserialize<'de>,
+    V: NestedDeserialize<'de>,
+    E: de::Error,
+{
+    let key = K::deserialize(KeyRefDeserializer::<E>::new(key)).ok()?;
+    let value = V::deserialize_nested_buffered::<E>(Buffered(value)).ok()?;
+    Some((key, value))
+}
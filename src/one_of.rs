@@ -0,0 +1,88 @@
+//! Values which may be of one of several types.
+
+use crate::content::{Content, ContentRefDeserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// A value which decodes to `A` if it can, and otherwise to `B`, as with an enum with
+/// `#[serde(untagged)]`. More types can be tried by nesting, e.g. `OneOf<A, OneOf<B, C>>`.
+///
+/// The value is buffered before it is decoded, so that it can be tried as each type in turn. If it
+/// decodes to neither, this fails as any other value which does not conform, so an entry of a
+/// [`SkippableMap`](crate::SkippableMap) with this as its value is skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{OneOf, SkippableMap};
+///
+/// let json = r#"{ "a": 1, "b": "2", "c": [3] }"#;
+/// let map: SkippableMap<String, OneOf<u64, String>> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(map.0["a"], OneOf::First(1));
+/// assert_eq!(map.0["b"], OneOf::Second(String::from("2")));
+/// assert!(!map.0.contains_key("c"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum OneOf<A, B> {
+    /// The value decoded to the first type
+    First(A),
+    /// The value did not decode to the first type, but did to the second
+    Second(B),
+}
+
+impl<A, B> OneOf<A, B> {
+    /// Returns the value if it decoded to the first type
+    pub fn first(self) -> Option<A> {
+        match self {
+            OneOf::First(a) => Some(a),
+            OneOf::Second(_) => None,
+        }
+    }
+
+    /// Returns the value if it decoded to the second type
+    pub fn second(self) -> Option<B> {
+        match self {
+            OneOf::First(_) => None,
+            OneOf::Second(b) => Some(b),
+        }
+    }
+
+    /// Converts the value into `T` whichever type it decoded to, e.g. to merge alternative
+    /// representations of the same value
+    pub fn unify<T>(self) -> T
+    where
+        A: Into<T>,
+        B: Into<T>,
+    {
+        match self {
+            OneOf::First(a) => a.into(),
+            OneOf::Second(b) => b.into(),
+        }
+    }
+}
+
+impl<'de, A, B> Deserialize<'de> for OneOf<A, B>
+where
+    A: Deserialize<'de>,
+    B: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let content = Content::deserialize(deserializer)?;
+        if let Ok(a) = A::deserialize(ContentRefDeserializer::<D::Error>::new(&content)) {
+            return Ok(OneOf::First(a));
+        }
+        if let Ok(b) = B::deserialize(ContentRefDeserializer::<D::Error>::new(&content)) {
+            return Ok(OneOf::Second(b));
+        }
+        Err(Error::custom(format_args!(
+            "data did not match {} or {}",
+            std::any::type_name::<A>(),
+            std::any::type_name::<B>(),
+        )))
+    }
+}
@@ -0,0 +1,121 @@
+//! Maps encoded as sequences of key/value pairs.
+
+use crate::content::{Content, ContentRefDeserializer, KeyRefDeserializer};
+use serde::de::{Deserialize, Error, SeqAccess};
+
+/// A shape of key/value pair which [`SkippableMap`](crate::SkippableMap) can accept when the input
+/// is a sequence of pairs rather than a map, e.g. `[["k", 1], ["j", 2]]`.
+///
+/// Sequences are rejected by default: the shapes to accept are chosen with
+/// [`SkippableMapSeed::pair_shapes`](crate::SkippableMapSeed::pair_shapes), e.g.
+/// [`PairShape::COMMON`].
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde_json;
+/// use skippable_map::{PairShape, SkippableMapSeed};
+///
+/// let json = r#"[["a", 1], ["b", "x"], { "key": "c", "value": 3 }, 4]"#;
+/// let map = SkippableMapSeed::<String, u64>::new()
+///     .pair_shapes(PairShape::COMMON)
+///     .deserialize(&mut serde_json::Deserializer::from_str(json))
+///     .unwrap();
+///
+/// assert_eq!(map.0.len(), 2);
+/// assert_eq!(map.0["c"], 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairShape {
+    /// A sequence of two elements, the key followed by the value
+    Tuple,
+    /// A map with an entry for the key and one for the value, with the given names. Any other
+    /// entries are ignored.
+    Object {
+        /// The name of the entry holding the key
+        key: &'static str,
+        /// The name of the entry holding the value
+        value: &'static str,
+    },
+}
+
+impl PairShape {
+    /// Both `[key, value]` and `{"key": key, "value": value}`
+    pub const COMMON: &'static [PairShape] = &[
+        PairShape::Tuple,
+        PairShape::Object {
+            key: "key",
+            value: "value",
+        },
+    ];
+
+    /// Returns the key and value of `pair` if it has this shape
+    pub(crate) fn split<'a, 'de>(
+        self,
+        pair: &'a Content<'de>,
+    ) -> Option<(&'a Content<'de>, &'a Content<'de>)> {
+        match (self, pair) {
+            (PairShape::Tuple, Content::Seq(elements)) => match elements.as_slice() {
+                [key, value] => Some((key, value)),
+                _ => None,
+            },
+            (PairShape::Object { key, value }, Content::Map(entries)) => {
+                let find = |name: &str| {
+                    entries
+                        .iter()
+                        .find(|(k, _)| k.as_str() == Some(name))
+                        .map(|(_, v)| v)
+                };
+                Some((find(key)?, find(value)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reads every element of `access` as a key/value pair of one of `shapes`, and otherwise behaves
+/// as [`visit_entries`](crate::visit_entries). Elements which are not pairs are skipped.
+pub(crate) fn visit_pairs<'de, A, K, V>(
+    mut access: A,
+    shapes: &[PairShape],
+    max_entries: Option<usize>,
+    keep: impl Fn(&K) -> bool,
+    mut insert: impl FnMut(usize, K, V) -> Result<(), A::Error>,
+    mut skip: impl FnMut(usize, Option<K>, A::Error) -> Result<(), A::Error>,
+) -> Result<(), A::Error>
+where
+    A: SeqAccess<'de>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    for index in 0.. {
+        let Some(pair) = access.next_element::<Content>()? else {
+            break;
+        };
+        if let Some(max) = max_entries.filter(|&max| index >= max) {
+            return Err(Error::custom(format_args!(
+                "map has more than {max} entries"
+            )));
+        }
+        let Some((raw_key, raw_value)) = shapes.iter().find_map(|shape| shape.split(&pair)) else {
+            // Not a pair (skip)
+            let error = Error::invalid_type(pair.unexpected(), &"a key/value pair");
+            skip(index, None, error)?;
+            continue;
+        };
+        match K::deserialize(KeyRefDeserializer::<A::Error>::new(raw_key)) {
+            // Key rejected (skip without decoding value)
+            Ok(key) if !keep(&key) => {}
+            Ok(key) => match V::deserialize(ContentRefDeserializer::<A::Error>::new(raw_value)) {
+                // Success in decoding pair (insert)
+                Ok(value) => insert(index, key, value)?,
+                // Error in decoding value (skip)
+                Err(e) => skip(index, Some(key), e)?,
+            },
+            // Error in decoding key (skip)
+            Err(e) => skip(index, None, e)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+//! Routing the entries of one map into several.
+
+use crate::{
+    content::{Content, ContentRefDeserializer, KeyRefDeserializer},
+    DuplicateKey, DuplicatePolicy, SkippableMap,
+};
+use serde::{
+    de::{value, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, marker::PhantomData};
+
+/// A buffered entry which is offered to each partition in turn
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct PartitionEntry<'a, 'de> {
+    key: &'a Content<'de>,
+    value: &'a Content<'de>,
+}
+
+/// A collection which takes the entries of a map that conform to it, so that several can be
+/// filled from the same input in a single pass with [`Partitioned`].
+///
+/// It is implemented for [`SkippableMap`], for tuples of up to eight partitions, and, with the
+/// `derive` feature, for structs with `#[derive(SkippablePartitions)]`.
+pub trait Partition<'de>: Sized {
+    /// Creates a partition with no entries
+    #[doc(hidden)]
+    fn empty() -> Self;
+
+    /// Takes `entry` if it conforms, returning whether it did, or returns an error if it conforms
+    /// but cannot be added
+    #[doc(hidden)]
+    fn offer(&mut self, entry: PartitionEntry<'_, 'de>) -> Result<bool, DuplicateKey>;
+}
+
+impl<'de, K, V, M, P> Partition<'de> for SkippableMap<K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    fn empty() -> Self {
+        SkippableMap::new(P::new_map(0))
+    }
+
+    fn offer(&mut self, entry: PartitionEntry<'_, 'de>) -> Result<bool, DuplicateKey> {
+        let Ok(key) = K::deserialize(KeyRefDeserializer::<value::Error>::new(entry.key)) else {
+            return Ok(false);
+        };
+        let Ok(value) = V::deserialize(ContentRefDeserializer::<value::Error>::new(entry.value))
+        else {
+            return Ok(false);
+        };
+        P::insert(&mut self.0, key, value)?;
+        Ok(true)
+    }
+}
+
+macro_rules! tuple_partition {
+    ($($name:ident)+) => {
+        impl<'de, $($name),+> Partition<'de> for ($($name,)+)
+        where
+            $($name: Partition<'de>,)+
+        {
+            fn empty() -> Self {
+                ($($name::empty(),)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn offer(&mut self, entry: PartitionEntry<'_, 'de>) -> Result<bool, DuplicateKey> {
+                let ($($name,)+) = self;
+                Ok(false $(|| $name.offer(entry)?)+)
+            }
+        }
+    };
+}
+
+tuple_partition!(A);
+tuple_partition!(A B);
+tuple_partition!(A B C);
+tuple_partition!(A B C D);
+tuple_partition!(A B C D E);
+tuple_partition!(A B C D E F);
+tuple_partition!(A B C D E F G);
+tuple_partition!(A B C D E F G H);
+
+/// Several [`Partition`]s filled from the same map in a single pass: each entry is buffered once
+/// and goes to the first partition it conforms to, so a heterogeneous map can be split by type
+/// without reading it again for each one.
+///
+/// Entries which conform to none of the partitions are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{Partitioned, SkippableMap};
+///
+/// let json = r#"{ "a": 1, "b": "x", "c": { "d": 2 }, "e": -1, "f": 3 }"#;
+/// let Partitioned((numbers, strings)): Partitioned<(
+///     SkippableMap<String, u64>,
+///     SkippableMap<String, String>,
+/// )> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(numbers.0.len(), 2);
+/// assert_eq!(strings.0["b"], "x");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Partitioned<T>(pub T);
+
+impl<T> Partitioned<T> {
+    /// Returns the wrapped partitions, consuming self
+    pub fn inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Partitioned<T>
+where
+    T: Partition<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_partitions(deserializer).map(Partitioned)
+    }
+}
+
+/// Fills the partitions of `T` from a map, as [`Partitioned`] does
+#[doc(hidden)]
+pub fn deserialize_partitions<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Partition<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(PartitionVisitor(PhantomData))
+}
+
+struct PartitionVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T> Visitor<'de> for PartitionVisitor<T>
+where
+    T: Partition<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut partitions = T::empty();
+        for index in 0.. {
+            let Some(key) = access.next_key::<Content>()? else {
+                break;
+            };
+            let value: Content = access.next_value()?;
+            let entry = PartitionEntry {
+                key: &key,
+                value: &value,
+            };
+            partitions.offer(entry).map_err(|error| error.at(index))?;
+        }
+        Ok(partitions)
+    }
+}
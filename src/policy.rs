@@ -0,0 +1,158 @@
+//! Policies for keys which appear more than once in the input.
+
+use crate::MapInsert;
+use serde::de;
+use std::fmt;
+
+/// How [`SkippableMap`](crate::SkippableMap) handles a key which appears more than once in the
+/// input, selected by its `P` parameter.
+///
+/// Only entries which decode to `(K, V)` are considered: an entry which is skipped does not count
+/// as an occurrence of its key.
+pub trait DuplicatePolicy<K, V, M> {
+    /// Creates an empty map, with space for at least `capacity` entries if the map supports it
+    fn new_map(capacity: usize) -> M;
+
+    /// Adds an entry to `map`, or returns an error if a duplicate key is not allowed, which stops
+    /// deserialization
+    fn insert(map: &mut M, key: K, value: V) -> Result<(), DuplicateKey>;
+}
+
+/// The error returned by a [`DuplicatePolicy`] which does not allow duplicate keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateKey;
+
+impl DuplicateKey {
+    /// Converts into a deserialization error for the entry at `index` in the input
+    pub(crate) fn at<E: de::Error>(self, index: usize) -> E {
+        E::custom(format_args!("{self} at entry {index}"))
+    }
+}
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("duplicate key")
+    }
+}
+
+impl std::error::Error for DuplicateKey {}
+
+/// The value of the last occurrence of a key is kept, as with inserting into a map. This is the
+/// default policy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastWins;
+
+impl<K, V, M> DuplicatePolicy<K, V, M> for LastWins
+where
+    M: MapInsert<K, V>,
+{
+    fn new_map(capacity: usize) -> M {
+        M::with_capacity(capacity)
+    }
+
+    fn insert(map: &mut M, key: K, value: V) -> Result<(), DuplicateKey> {
+        map.insert(key, value);
+        Ok(())
+    }
+}
+
+/// The value of the first occurrence of a key is kept, and later ones are ignored
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{FirstWins, SkippableMap};
+/// use std::collections::HashMap;
+///
+/// let json = r#"{ "a": "x", "a": 1, "a": 2 }"#;
+/// let map: SkippableMap<String, u64, HashMap<String, u64>, FirstWins> =
+///     serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(map.0["a"], 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstWins;
+
+impl<K, V, M> DuplicatePolicy<K, V, M> for FirstWins
+where
+    M: MapInsert<K, V>,
+{
+    fn new_map(capacity: usize) -> M {
+        M::with_capacity(capacity)
+    }
+
+    fn insert(map: &mut M, key: K, value: V) -> Result<(), DuplicateKey> {
+        if map.get_mut(&key).is_none() {
+            map.insert(key, value);
+        }
+        Ok(())
+    }
+}
+
+/// A key appearing more than once is an error, which gives the position in the input of the entry
+/// which repeated it
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{DenyDuplicates, SkippableMap};
+/// use std::collections::HashMap;
+///
+/// let json = r#"{ "a": 1, "a": 2 }"#;
+/// let map: Result<SkippableMap<String, u64, HashMap<String, u64>, DenyDuplicates>, _> =
+///     serde_json::from_str(json);
+///
+/// assert!(map.is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyDuplicates;
+
+impl<K, V, M> DuplicatePolicy<K, V, M> for DenyDuplicates
+where
+    M: MapInsert<K, V>,
+{
+    fn new_map(capacity: usize) -> M {
+        M::with_capacity(capacity)
+    }
+
+    fn insert(map: &mut M, key: K, value: V) -> Result<(), DuplicateKey> {
+        if map.get_mut(&key).is_some() {
+            return Err(DuplicateKey);
+        }
+        map.insert(key, value);
+        Ok(())
+    }
+}
+
+/// The values of every occurrence of a key are kept, in order, so the map must have values of
+/// type `Vec<V>`
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::{CollectAll, SkippableMap};
+/// use std::collections::HashMap;
+///
+/// let json = r#"{ "a": 1, "b": 2, "a": "x", "a": 3 }"#;
+/// let map: SkippableMap<String, u64, HashMap<String, Vec<u64>>, CollectAll> =
+///     serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(map.0["a"], vec![1, 3]);
+/// assert_eq!(map.0["b"], vec![2]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollectAll;
+
+impl<K, V, M> DuplicatePolicy<K, V, M> for CollectAll
+where
+    M: MapInsert<K, Vec<V>>,
+{
+    fn new_map(capacity: usize) -> M {
+        M::with_capacity(capacity)
+    }
+
+    fn insert(map: &mut M, key: K, value: V) -> Result<(), DuplicateKey> {
+        match map.get_mut(&key) {
+            Some(values) => values.push(value),
+            None => map.insert(key, vec![value]),
+        }
+        Ok(())
+    }
+}
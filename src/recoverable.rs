@@ -0,0 +1,124 @@
+//! Classifying errors as recoverable or fatal for specific formats.
+
+/// Errors which can tell whether reading can continue after them, because the value which caused
+/// them was read to its end, such as a number which is out of range, or not, such as a syntax
+/// error, unexpected EOF or an I/O error.
+///
+/// [`SkippableMap`](crate::SkippableMap) and the other types in this crate tell the two apart
+/// without this trait, for any format, by buffering each value before decoding it. This trait is
+/// for formats which cannot be buffered that way, and is used by
+/// [`SkippableMapSeed::deserialize_recoverable`](crate::SkippableMapSeed::deserialize_recoverable),
+/// which only skips the entries whose error decoding them is recoverable.
+///
+/// It is implemented for the errors of several formats, each behind a feature:
+///
+/// - `json`: `serde_json::Error`, using its `classify` method. A value which is left partly read
+///   makes the next read fail with a syntax error, which is returned.
+/// - `msgpack`: `rmp_serde::decode::Error`
+/// - `cbor`: `ciborium::de::Error`
+/// - `bincode`: `bincode::Error`
+/// - `postcard`: `postcard::Error`
+///
+/// The `msgpack`, `cbor`, `bincode` and `postcard` errors raised by a [`Deserialize`](serde::Deserialize) implementation
+/// itself, e.g. to validate a value, are recoverable. When one is raised partway through an array
+/// or map, its remaining elements are left unread and misread as the entries after it, so only
+/// values which are read as a whole before they are checked can be skipped reliably.
+///
+/// It is not implemented for the errors of TOML and YAML, whose deserializers either read their
+/// entries with another error type or cannot say whether an error left a value partly read. Both
+/// describe their own types, so [`SkippableMap`](crate::SkippableMap) reads them safely.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "json")]
+/// # {
+/// use skippable_map::Recoverable;
+///
+/// let data = serde_json::from_str::<u64>(r#""x""#).unwrap_err();
+/// let syntax = serde_json::from_str::<u64>(r#""x"#).unwrap_err();
+///
+/// assert!(data.is_recoverable());
+/// assert!(!syntax.is_recoverable());
+/// # }
+/// ```
+pub trait Recoverable {
+    /// Returns `true` if the value which caused the error was read to its end, so that it can be
+    /// skipped and reading can continue
+    fn is_recoverable(&self) -> bool;
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl Recoverable for serde_json::Error {
+    fn is_recoverable(&self) -> bool {
+        self.classify() == serde_json::error::Category::Data
+    }
+}
+
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+impl Recoverable for rmp_serde::decode::Error {
+    fn is_recoverable(&self) -> bool {
+        use rmp_serde::decode::Error;
+
+        match self {
+            // A number which does not fit, and errors raised with `de::Error::custom`, are raised
+            // after the value is read
+            Error::OutOfRange | Error::Syntax(_) => true,
+            // A marker of the wrong type is raised before the value after it is read
+            Error::TypeMismatch(_)
+            | Error::LengthMismatch(_)
+            | Error::InvalidMarkerRead(_)
+            | Error::InvalidDataRead(_)
+            | Error::Uncategorized(_)
+            | Error::Utf8Error(_)
+            | Error::DepthLimitExceeded => false,
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+impl<T> Recoverable for ciborium::de::Error<T> {
+    fn is_recoverable(&self) -> bool {
+        matches!(self, ciborium::de::Error::Semantic(..))
+    }
+}
+
+#[cfg(feature = "bincode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bincode")))]
+impl Recoverable for bincode::Error {
+    fn is_recoverable(&self) -> bool {
+        use bincode::ErrorKind;
+
+        match **self {
+            // A string or bool is read whole before it is checked
+            ErrorKind::InvalidUtf8Encoding(_)
+            | ErrorKind::InvalidBoolEncoding(_)
+            | ErrorKind::Custom(_) => true,
+            // A char can fail after reading only some of its bytes
+            ErrorKind::InvalidCharEncoding
+            | ErrorKind::Io(_)
+            | ErrorKind::InvalidTagEncoding(_)
+            | ErrorKind::DeserializeAnyNotSupported
+            | ErrorKind::SizeLimit
+            | ErrorKind::SequenceMustHaveLength => false,
+        }
+    }
+}
+
+#[cfg(feature = "postcard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard")))]
+impl Recoverable for postcard::Error {
+    fn is_recoverable(&self) -> bool {
+        use postcard::Error;
+
+        // A string or bool is read whole before it is checked, while a char can fail after
+        // reading only its length
+        matches!(
+            self,
+            Error::DeserializeBadUtf8 | Error::DeserializeBadBool | Error::SerdeDeCustom
+        )
+    }
+}
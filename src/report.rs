@@ -0,0 +1,154 @@
+//! Diagnostics for entries skipped while deserializing.
+
+use crate::{DuplicatePolicy, LastWins, SkippableMap, SkippableMapSeed};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
+
+/// The error which caused an entry to be skipped, as reported by the deserializer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SkipError(String);
+
+impl SkipError {
+    pub(crate) fn new(error: impl fmt::Display) -> Self {
+        Self(error.to_string())
+    }
+
+    /// Returns the error message
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SkipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SkipError {}
+
+/// A single entry which was skipped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedEntry<K> {
+    /// Position of the entry in the input, counting from zero and including entries which were
+    /// kept
+    pub index: usize,
+    /// The key of the entry, if it decoded to `K` (i.e. only the value failed)
+    pub key: Option<K>,
+    /// Why the entry was skipped
+    pub error: SkipError,
+}
+
+/// Every entry skipped while deserializing a map, in the order they were encountered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SkipReport<K>(pub Vec<SkippedEntry<K>>);
+
+impl<K> Default for SkipReport<K> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<K> SkipReport<K> {
+    /// Number of entries which were skipped
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no entries were skipped
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the skipped entries
+    pub fn iter(&self) -> std::slice::Iter<'_, SkippedEntry<K>> {
+        self.0.iter()
+    }
+}
+
+impl<K> IntoIterator for SkipReport<K> {
+    type Item = SkippedEntry<K>;
+    type IntoIter = std::vec::IntoIter<SkippedEntry<K>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, K> IntoIterator for &'a SkipReport<K> {
+    type Item = &'a SkippedEntry<K>;
+    type IntoIter = std::slice::Iter<'a, SkippedEntry<K>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// A [`SkippableMap`] which also records a [`SkipReport`] of every entry it skipped.
+///
+/// A key which appears more than once among the kept entries is handled by the
+/// [`DuplicatePolicy`] `P`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::SkippableMapWithReport;
+///
+/// let json = r#"{ "string": "b", "number": 1, "negative_number": -44}"#;
+/// let with_report: SkippableMapWithReport<String, u64> = serde_json::from_str(json).unwrap();
+/// let (just_numbers, report) = with_report.into_parts();
+///
+/// assert_eq!(just_numbers.0.len(), 1);
+/// assert_eq!(report.len(), 2);
+///
+/// let skipped = &report.0[1];
+/// assert_eq!(skipped.index, 2);
+/// assert_eq!(skipped.key.as_deref(), Some("negative_number"));
+/// assert!(skipped.error.message().contains("-44"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SkippableMapWithReport<K, V, M = HashMap<K, V>, P = LastWins> {
+    /// Entries which decoded to `(K, V)`
+    pub map: SkippableMap<K, V, M, P>,
+    /// Entries which were skipped
+    pub report: SkipReport<K>,
+}
+
+impl<K, V, M, P> SkippableMapWithReport<K, V, M, P> {
+    /// Returns the wrapped inner map, consuming self and discarding the report
+    pub fn inner(self) -> M {
+        self.map.0
+    }
+
+    /// Splits into the [`SkippableMap`] of kept entries and the [`SkipReport`] of skipped ones
+    pub fn into_parts(self) -> (SkippableMap<K, V, M, P>, SkipReport<K>) {
+        (self.map, self.report)
+    }
+}
+
+impl<K, V, M, P> AsRef<M> for SkippableMapWithReport<K, V, M, P> {
+    fn as_ref(&self) -> &M {
+        &self.map.0
+    }
+}
+
+impl<'de, K, V, M, P> Deserialize<'de> for SkippableMapWithReport<K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut report = SkipReport::default();
+        let map = SkippableMapSeed::new()
+            .collect_skipped(&mut report)
+            .deserialize(deserializer)?;
+        Ok(SkippableMapWithReport { map, report })
+    }
+}
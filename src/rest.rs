@@ -0,0 +1,102 @@
+//! Keeping skipped entries as JSON values.
+
+use crate::{
+    content::{Content, ContentRefDeserializer, KeyRefDeserializer},
+    DuplicatePolicy, LastWins, SkippableMap, SkippableMapSeed,
+};
+use serde::{
+    de::{self, DeserializeSeed},
+    Deserialize,
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// A [`SkippableMap`] which also keeps every entry it skipped as a JSON [`Value`], so that they
+/// can be read another way or forwarded untouched without parsing the input twice.
+///
+/// The keys of skipped entries are kept as strings, and a key which is not a string (as YAML
+/// allows) is written as JSON. An entry which cannot be represented as JSON at all, such as one
+/// holding raw bytes, is dropped. Of skipped entries with the same key only the last is kept,
+/// while repeated keys among the other entries are handled by the [`DuplicatePolicy`] `P`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json::{self, json};
+/// use skippable_map::SkippableMapWithRest;
+///
+/// let json = r#"{ "string": "b", "number": 1, "nested": { "a": [1, 2] } }"#;
+/// let with_rest: SkippableMapWithRest<String, u64> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(with_rest.map.0["number"], 1);
+/// assert_eq!(with_rest.rest()["string"], "b");
+/// assert_eq!(with_rest.rest()["nested"], json!({ "a": [1, 2] }));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Default)]
+pub struct SkippableMapWithRest<K, V, M = HashMap<K, V>, P = LastWins> {
+    /// Entries which decoded to `(K, V)`
+    pub map: SkippableMap<K, V, M, P>,
+    /// Entries which were skipped
+    pub rest: Map<String, Value>,
+}
+
+impl<K, V, M, P> SkippableMapWithRest<K, V, M, P> {
+    /// Returns the wrapped inner map, consuming self and discarding the skipped entries
+    pub fn inner(self) -> M {
+        self.map.0
+    }
+
+    /// Returns the entries which were skipped
+    pub fn rest(&self) -> &Map<String, Value> {
+        &self.rest
+    }
+
+    /// Splits into the [`SkippableMap`] of kept entries and the skipped ones
+    pub fn into_parts(self) -> (SkippableMap<K, V, M, P>, Map<String, Value>) {
+        (self.map, self.rest)
+    }
+}
+
+impl<K, V, M, P> AsRef<M> for SkippableMapWithRest<K, V, M, P> {
+    fn as_ref(&self) -> &M {
+        &self.map.0
+    }
+}
+
+/// Converts a buffered entry to JSON, or returns `None` if it cannot be represented
+fn to_json<E>(key: &Content, value: &Content) -> Option<(String, Value)>
+where
+    E: de::Error,
+{
+    let key = match String::deserialize(KeyRefDeserializer::<E>::new(key)) {
+        Ok(key) => key,
+        Err(_) => Value::deserialize(ContentRefDeserializer::<E>::new(key))
+            .ok()?
+            .to_string(),
+    };
+    let value = Value::deserialize(ContentRefDeserializer::<E>::new(value)).ok()?;
+    Some((key, value))
+}
+
+impl<'de, K, V, M, P> Deserialize<'de> for SkippableMapWithRest<K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut raw = Vec::new();
+        let map = SkippableMapSeed::new()
+            .keep_raw(&mut raw)
+            .deserialize(deserializer)?;
+        let rest = raw
+            .iter()
+            .filter_map(|(_, key, value)| to_json::<D::Error>(key, value))
+            .collect();
+        Ok(SkippableMapWithRest { map, rest })
+    }
+}
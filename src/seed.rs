@@ -0,0 +1,298 @@
+use crate::{
+    content::Content, direct::DirectVisitor, DuplicatePolicy, LastWins, PairShape, RawEntry,
+    Recoverable, SkipError, SkipReport, SkippableMap, SkippableMapVisitor, SkippedEntry,
+};
+use serde::{
+    de::{DeserializeSeed, Error},
+    Deserialize, Deserializer,
+};
+use std::{collections::HashMap, fmt, marker::PhantomData};
+
+/// A [`DeserializeSeed`] for [`SkippableMap`], which allows the skipping behaviour to be configured
+/// at runtime.
+///
+/// With the default options this behaves exactly as [`SkippableMap`]'s implementation of
+/// [`Deserialize`](serde::Deserialize).
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde_json;
+/// use skippable_map::{SkipReport, SkippableMap, SkippableMapSeed};
+///
+/// let json = r#"{ "string": "b", "number": 1, "other_number": 2, "negative_number": -44}"#;
+///
+/// let mut report = SkipReport::default();
+/// let seed = SkippableMapSeed::<String, u64>::new()
+///     .allow_keys([String::from("number"), String::from("negative_number")])
+///     .collect_skipped(&mut report);
+/// let map = seed
+///     .deserialize(&mut serde_json::Deserializer::from_str(json))
+///     .unwrap();
+///
+/// assert_eq!(map.0.len(), 1);
+/// assert_eq!(map.0["number"], 1);
+/// assert_eq!(report.0[0].key.as_deref(), Some("negative_number"));
+///
+/// // Strict mode fails on the first entry which would have been skipped
+/// let strict = SkippableMapSeed::<String, u64>::new().strict();
+/// assert!(strict
+///     .deserialize(&mut serde_json::Deserializer::from_str(json))
+///     .is_err());
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct SkippableMapSeed<'a, K, V, M = HashMap<K, V>, P = LastWins> {
+    options: Options<'a, K>,
+    marker: PhantomData<fn() -> SkippableMap<K, V, M, P>>,
+}
+
+impl<'a, K, V, M, P> SkippableMapSeed<'a, K, V, M, P> {
+    /// Creates a seed with the default options
+    pub fn new() -> Self {
+        Self {
+            options: Options::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Fails with an error if more than `max` entries would be skipped
+    pub fn max_skips(mut self, max: usize) -> Self {
+        self.options.skips.max = Some(max);
+        self
+    }
+
+    /// Fails with an error if the input has more than `max` entries, counting those which are
+    /// skipped or filtered out, so that the work done on untrusted input is bounded
+    pub fn max_entries(mut self, max: usize) -> Self {
+        self.options.max_entries = Some(max);
+        self
+    }
+
+    /// Sets the shapes of key/value pair which are accepted when the input is a sequence of pairs
+    /// rather than a map, in the order they are tried. By default none are, and sequences are
+    /// rejected.
+    pub fn pair_shapes(mut self, shapes: &'a [PairShape]) -> Self {
+        self.options.pair_shapes = shapes;
+        self
+    }
+
+    /// Fails with an error rather than skipping any entry, which is useful in tests to check that
+    /// input conforms entirely. Equivalent to `max_skips(0)`.
+    pub fn strict(self) -> Self {
+        self.max_skips(0)
+    }
+
+    /// Only keeps entries whose key satisfies `predicate`.
+    ///
+    /// The value of an entry whose key is rejected is skipped over without being decoded, which is
+    /// much faster than decoding it when only a few keys of a large map are wanted. Such entries
+    /// are not counted as skipped. This replaces any previous filter.
+    ///
+    /// ```rust
+    /// use serde::de::DeserializeSeed;
+    /// use serde_json;
+    /// use skippable_map::SkippableMapSeed;
+    ///
+    /// let json = r#"{ "id_1": 1, "blob": [[1, 2], {"x": "y"}], "id_2": 2 }"#;
+    /// let map = SkippableMapSeed::<String, u64>::new()
+    ///     .filter_keys(|key| key.starts_with("id_"))
+    ///     .strict()
+    ///     .deserialize(&mut serde_json::Deserializer::from_str(json))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(map.0.len(), 2);
+    /// ```
+    pub fn filter_keys<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&K) -> bool + 'a,
+    {
+        self.options.key_filter = Some(Box::new(predicate));
+        self
+    }
+
+    /// Only keeps entries whose key is one of `keys`, as with [`filter_keys`](Self::filter_keys)
+    pub fn allow_keys<I>(self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: PartialEq + 'a,
+    {
+        let keys: Vec<K> = keys.into_iter().collect();
+        self.filter_keys(move |key| keys.contains(key))
+    }
+
+    /// Records every skipped entry in `report`
+    pub fn collect_skipped(mut self, report: &'a mut SkipReport<K>) -> Self {
+        self.options.skips.report = Some(report);
+        self
+    }
+
+    /// Keeps every skipped entry of a map in `raw` as it was buffered, so that it can be kept in
+    /// another form
+    pub(crate) fn keep_raw(mut self, raw: &'a mut Vec<RawSkipped>) -> Self {
+        self.options.skips.raw = Some(raw);
+        self
+    }
+}
+
+impl<'a, K, V, M, P> Default for SkippableMapSeed<'a, K, V, M, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, 'de, K, V, M, P> SkippableMapSeed<'a, K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    /// Deserializes without buffering each key and value, which formats that do not describe their
+    /// own types, such as `bincode` and `postcard`, need, as they cannot read a value without being
+    /// told its type. Use [`DeserializeSeed::deserialize`] for any other format.
+    ///
+    /// Keys and values are decoded straight from the input, so the format itself has to say
+    /// whether reading can continue after an error: an entry is only skipped if its error is
+    /// [`Recoverable`], and any other error is returned. The value of an entry whose key is
+    /// rejected by [`filter_keys`](Self::filter_keys) is still decoded, and pair shapes are not
+    /// accepted.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// use serde::{de::Error, Deserialize, Deserializer};
+    /// use skippable_map::SkippableMapSeed;
+    ///
+    /// struct Port(u16);
+    ///
+    /// impl<'de> Deserialize<'de> for Port {
+    ///     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    ///         match u16::deserialize(deserializer)? {
+    ///             0 => Err(D::Error::custom("port 0 is reserved")),
+    ///             port => Ok(Port(port)),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let seed = || SkippableMapSeed::<String, Port>::new();
+    /// let json = r#"{ "a": 80, "b": 0, "c": 70000 }"#;
+    /// let map = seed()
+    ///     .deserialize_recoverable(&mut serde_json::Deserializer::from_str(json))
+    ///     .unwrap();
+    /// assert_eq!(map.0.len(), 1);
+    ///
+    /// // A syntax error cannot be skipped
+    /// let json = r#"{ "a": 80, "b": 0 "c": 81 }"#;
+    /// let result = seed().deserialize_recoverable(&mut serde_json::Deserializer::from_str(json));
+    /// assert!(result.is_err());
+    /// # }
+    /// ```
+    pub fn deserialize_recoverable<D>(
+        self,
+        deserializer: D,
+    ) -> Result<SkippableMap<K, V, M, P>, D::Error>
+    where
+        D: Deserializer<'de>,
+        D::Error: Recoverable + 'static,
+    {
+        deserializer.deserialize_map(DirectVisitor::<_, _, _, _, D::Error>::new(self.options))
+    }
+}
+
+impl<'a, 'de, K, V, M, P> DeserializeSeed<'de> for SkippableMapSeed<'a, K, V, M, P>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    P: DuplicatePolicy<K, V, M>,
+{
+    type Value = SkippableMap<K, V, M, P>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let visitor = SkippableMapVisitor::with_options(self.options);
+        // Only formats which are told that any value is expected will offer a sequence
+        if visitor.options.pair_shapes.is_empty() {
+            deserializer.deserialize_map(visitor)
+        } else {
+            deserializer.deserialize_any(visitor)
+        }
+    }
+}
+
+pub(crate) type KeyFilter<'a, K> = Box<dyn Fn(&K) -> bool + 'a>;
+
+/// A skipped entry as it was buffered, along with its position in the input
+pub(crate) type RawSkipped = (usize, Content<'static>, Content<'static>);
+
+/// Runtime configuration of [`SkippableMapVisitor`]
+pub(crate) struct Options<'a, K> {
+    /// Entries whose key does not satisfy this are dropped
+    pub(crate) key_filter: Option<KeyFilter<'a, K>>,
+    /// Reading more entries than this is an error
+    pub(crate) max_entries: Option<usize>,
+    /// The shapes of key/value pair accepted when the input is a sequence
+    pub(crate) pair_shapes: &'a [PairShape],
+    pub(crate) skips: Skips<'a, K>,
+}
+
+impl<'a, K> Default for Options<'a, K> {
+    fn default() -> Self {
+        Self {
+            key_filter: None,
+            max_entries: None,
+            pair_shapes: &[],
+            skips: Skips {
+                count: 0,
+                max: None,
+                report: None,
+                raw: None,
+            },
+        }
+    }
+}
+
+/// Keeps track of skipped entries
+pub(crate) struct Skips<'a, K> {
+    count: usize,
+    max: Option<usize>,
+    report: Option<&'a mut SkipReport<K>>,
+    raw: Option<&'a mut Vec<RawSkipped>>,
+}
+
+impl<'a, K> Skips<'a, K> {
+    /// Records a skipped entry, or returns an error if too many have been skipped
+    pub(crate) fn record<E>(
+        &mut self,
+        index: usize,
+        key: Option<K>,
+        error: impl fmt::Display,
+    ) -> Result<(), E>
+    where
+        E: Error,
+    {
+        self.count += 1;
+        if let Some(max) = self.max.filter(|&max| self.count > max) {
+            return Err(E::custom(format_args!(
+                "entry {index} could not be decoded ({error}), and at most {max} entries may be \
+                 skipped"
+            )));
+        }
+        if let Some(report) = self.report.as_deref_mut() {
+            report.0.push(SkippedEntry {
+                index,
+                key,
+                error: SkipError::new(error),
+            });
+        }
+        Ok(())
+    }
+
+    /// Keeps a skipped entry as it was buffered, if asked to
+    pub(crate) fn record_raw(&mut self, index: usize, (key, value): RawEntry) {
+        if let Some(raw) = self.raw.as_deref_mut() {
+            raw.push((index, key.to_static(), value.to_static()));
+        }
+    }
+}
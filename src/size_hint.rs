@@ -0,0 +1,16 @@
+//! Bounds on preallocation, as the length a format reports for a map or sequence may come straight
+//! from the input and so cannot be trusted.
+
+use std::mem;
+
+/// The most memory which will be allocated up front for a collection, however long it claims to be
+const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+
+/// Returns the capacity to preallocate for a collection of `T` given its reported length, as with
+/// serde's own private `size_hint::cautious`. Collections longer than this still grow as needed.
+pub(crate) fn cautious<T>(hint: Option<usize>) -> usize {
+    match mem::size_of::<T>() {
+        0 => 0,
+        size => hint.unwrap_or(0).min(MAX_PREALLOC_BYTES / size),
+    }
+}
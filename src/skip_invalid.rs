@@ -0,0 +1,115 @@
+use crate::{SkippableMap, SkippableVec};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+/// Adapter for [`serde_with::serde_as`](https://docs.rs/serde_with/latest/serde_with/attr.serde_as.html),
+/// which deserializes maps as [`SkippableMap`] and sequences
+/// as [`SkippableVec`], skipping any entry or element which does not conform.
+///
+/// This is implemented for [`HashMap`], [`BTreeMap`], [`Vec`], and with the `indexmap` feature
+/// `IndexMap`. Serialization is unchanged. As with other `serde_with` adapters, it can be nested
+/// inside other types, e.g. `HashMap<_, SkipInvalid>` keeps every entry of a map but skips the
+/// invalid elements of each value.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_json;
+/// use serde_with::serde_as;
+/// use skippable_map::SkipInvalid;
+/// use std::collections::{BTreeMap, HashMap};
+///
+/// #[serde_as]
+/// #[derive(Deserialize)]
+/// struct Data {
+///     #[serde_as(as = "SkipInvalid")]
+///     numbers: BTreeMap<String, u64>,
+///     #[serde_as(as = "HashMap<_, SkipInvalid>")]
+///     lists: HashMap<String, Vec<u64>>,
+/// }
+///
+/// let json = r#"{
+///     "numbers": { "string": "b", "number": 1 },
+///     "lists": { "a": [1, "x", 2], "b": [-1] }
+/// }"#;
+/// let data: Data = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(data.numbers, BTreeMap::from([(String::from("number"), 1)]));
+/// assert_eq!(data.lists["a"], vec![1, 2]);
+/// assert!(data.lists["b"].is_empty());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_with")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SkipInvalid;
+
+impl<'de, K, V, S> DeserializeAs<'de, HashMap<K, V, S>> for SkipInvalid
+where
+    K: Deserialize<'de> + Hash + Eq,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<HashMap<K, V, S>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <SkippableMap<K, V, HashMap<K, V, S>>>::deserialize(deserializer).map(SkippableMap::inner)
+    }
+}
+
+impl<'de, K, V> DeserializeAs<'de, BTreeMap<K, V>> for SkipInvalid
+where
+    K: Deserialize<'de> + Ord,
+    V: Deserialize<'de>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <SkippableMap<K, V, BTreeMap<K, V>>>::deserialize(deserializer).map(SkippableMap::inner)
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<'de, K, V, S> DeserializeAs<'de, indexmap::IndexMap<K, V, S>> for SkipInvalid
+where
+    K: Deserialize<'de> + Hash + Eq,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<indexmap::IndexMap<K, V, S>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <SkippableMap<K, V, indexmap::IndexMap<K, V, S>>>::deserialize(deserializer)
+            .map(SkippableMap::inner)
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, Vec<T>> for SkipInvalid
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SkippableVec::deserialize(deserializer).map(SkippableVec::inner)
+    }
+}
+
+impl<T> SerializeAs<T> for SkipInvalid
+where
+    T: Serialize,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        source.serialize(serializer)
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    content::{Content, ContentRefDeserializer},
+    size_hint,
+};
+use serde::{de::Visitor, Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// A wrapper around [`Vec`] with a custom implementation of [`Deserialize`], which is to
+/// sequences what [`SkippableMap`](crate::SkippableMap) is to maps.
+/// The implementation goes through the sequence to be deserialized, and skips any element which
+/// does not conform to `T`, keeping the rest in order.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_json;
+/// use skippable_map::SkippableVec;
+///
+/// let json = r#"[1, "b", 2, -44, {"c": 3}, 3]"#;
+/// // SkippableVec<u64> will skip the string, the negative number and the object
+/// let just_numbers: SkippableVec<u64> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(just_numbers.as_ref(), &vec![1, 2, 3]);
+/// assert_eq!(just_numbers.0, vec![1, 2, 3]);
+/// // Consumes just_numbers to produce inner Vec
+/// assert_eq!(just_numbers.inner(), vec![1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct SkippableVec<T>(pub Vec<T>);
+
+impl<T> SkippableVec<T> {
+    /// Returns the wrapped inner [`Vec`], consuming self
+    pub fn inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+struct SkippableVecVisitor<T> {
+    marker: PhantomData<fn() -> SkippableVec<T>>,
+}
+
+impl<T> SkippableVecVisitor<T> {
+    fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for SkippableVecVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = SkippableVec<T>;
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a sequence which contains some elements of {}",
+            std::any::type_name::<T>(),
+        )
+    }
+
+    fn visit_seq<A>(self, access: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(size_hint::cautious::<T>(access.size_hint()));
+        visit_elements(access, |element| vec.push(element))?;
+        Ok(SkippableVec(vec))
+    }
+}
+
+/// Reads every element of `access`, passing those which decode to `T` to `push` and dropping the
+/// rest.
+///
+/// As with [`visit_entries`](crate::visit_entries), elements are buffered first so that only
+/// well-formed elements are skipped, and errors in the input itself are returned.
+pub(crate) fn visit_elements<'de, A, T>(
+    mut access: A,
+    mut push: impl FnMut(T),
+) -> std::result::Result<(), A::Error>
+where
+    A: serde::de::SeqAccess<'de>,
+    T: Deserialize<'de>,
+{
+    while let Some(element) = access.next_element::<Content>()? {
+        // Elements which do not decode to T are skipped
+        if let Ok(element) = T::deserialize(ContentRefDeserializer::<A::Error>::new(&element)) {
+            push(element);
+        }
+    }
+    Ok(())
+}
+
+impl<T> From<SkippableVec<T>> for Vec<T> {
+    fn from(value: SkippableVec<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T> AsRef<Vec<T>> for SkippableVec<T> {
+    fn as_ref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for SkippableVec<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SkippableVecVisitor::new())
+    }
+}
@@ -0,0 +1,35 @@
+use skippable_map::{AlwaysSkippableMap, DenyDuplicates};
+use std::collections::HashMap;
+
+type Numbers = AlwaysSkippableMap<String, u64>;
+
+#[test]
+fn non_map_input_is_an_empty_map() {
+    for json in ["null", r#""string""#, "1", "true", "[1, 2]", "[]"] {
+        let map: Numbers = serde_json::from_str(json).unwrap();
+        assert!(map.map.0.is_empty(), "{json}");
+        assert!(map.error.is_some(), "{json}");
+    }
+
+    let map: Numbers = serde_json::from_str("null").unwrap();
+    assert!(map.error.unwrap().message().contains("null"));
+
+    let map: Numbers = serde_yaml::from_str("just a string").unwrap();
+    assert!(map.map.0.is_empty());
+    assert!(map.error.is_some());
+}
+
+#[test]
+fn map_input_is_skipped_as_normal() {
+    let map: Numbers = serde_json::from_str(r#"{"a": 1, "b": "x"}"#).unwrap();
+    assert_eq!(map.map.0.len(), 1);
+    assert!(map.error.is_none());
+}
+
+#[test]
+fn errors_in_a_map_are_returned() {
+    // Only input which is not a map becomes an empty one
+    let result: Result<AlwaysSkippableMap<String, u64, HashMap<_, _>, DenyDuplicates>, _> =
+        serde_json::from_str(r#"{"a": 1, "a": 2}"#);
+    assert!(result.unwrap_err().to_string().contains("duplicate"));
+}
@@ -0,0 +1,100 @@
+use serde::de::DeserializeOwned;
+use skippable_map::{Coerce, SkippableMap};
+use std::collections::HashMap;
+
+fn coerced<T: DeserializeOwned>(json: &str) -> HashMap<String, T> {
+    let map: SkippableMap<String, Coerce<T>> = serde_json::from_str(json).unwrap();
+    map.inner().into_iter().map(|(k, v)| (k, v.0)).collect()
+}
+
+#[test]
+fn integers() {
+    let json = r#"{"a": 42, "b": "42", "c": 42.0, "d": "42.0", "e": 4.2, "f": "4.2",
+        "g": "x", "h": -1, "i": "-1", "j": 1e300, "k": true, "l": null}"#;
+    let map: HashMap<String, u64> = coerced(json);
+
+    let mut keys: Vec<_> = map.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["a", "b", "c", "d"]);
+    assert!(map.values().all(|&n| n == 42));
+
+    let map: HashMap<String, i8> = coerced(json);
+    assert_eq!(map.len(), 6);
+    assert_eq!(map["i"], -1);
+}
+
+#[test]
+fn integer_ranges() {
+    let json = r#"{"a": 255.0, "b": 256.0, "c": "255", "d": "256", "e": -0.0}"#;
+    let map: HashMap<String, u8> = coerced(json);
+    assert_eq!(
+        map,
+        HashMap::from([
+            ("a".to_string(), 255),
+            ("c".to_string(), 255),
+            ("e".to_string(), 0)
+        ])
+    );
+
+    // 2^64 is the first float out of range
+    let json = r#"{"a": 18446744073709551615, "b": 18446744073709551616.0}"#;
+    let map: HashMap<String, u64> = coerced(json);
+    assert_eq!(map, HashMap::from([("a".to_string(), u64::MAX)]));
+}
+
+#[test]
+fn floats() {
+    let json = r#"{"a": 1.5, "b": "1.5", "c": 2, "d": "2", "e": "x", "f": false, "g": "NaN",
+        "h": "inf", "i": "-infinity", "j": "1e400"}"#;
+    let map: HashMap<String, f64> = coerced(json);
+    assert_eq!(
+        map,
+        HashMap::from([
+            ("a".to_string(), 1.5),
+            ("b".to_string(), 1.5),
+            ("c".to_string(), 2.0),
+            ("d".to_string(), 2.0)
+        ])
+    );
+}
+
+#[test]
+fn booleans() {
+    let json = r#"{"a": true, "b": "false", "c": "True", "d": 1, "e": "yes"}"#;
+    let map: HashMap<String, bool> = coerced(json);
+    assert_eq!(
+        map,
+        HashMap::from([("a".to_string(), true), ("b".to_string(), false)])
+    );
+}
+
+#[test]
+fn options() {
+    let json = r#"{"a": null, "b": "3", "c": 3.0, "d": "x"}"#;
+    let map: HashMap<String, Option<u32>> = coerced(json);
+    assert_eq!(
+        map,
+        HashMap::from([
+            ("a".to_string(), None),
+            ("b".to_string(), Some(3)),
+            ("c".to_string(), Some(3))
+        ])
+    );
+}
+
+#[test]
+fn other_formats() {
+    let yaml = "a: '7'\nb: 7.0\nc: 'true'\n";
+    let map: SkippableMap<String, Coerce<u16>> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(map.0.len(), 2);
+
+    let toml = "a = \"7\"\nb = 7.0\nc = 7.5\n";
+    let map: SkippableMap<String, Coerce<i32>> = toml::from_str(toml).unwrap();
+    assert_eq!(map.0.len(), 2);
+}
+
+#[test]
+fn round_trip() {
+    let value: Coerce<u64> = serde_json::from_str(r#""42""#).unwrap();
+    assert_eq!(serde_json::to_string(&value).unwrap(), "42");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "derive")]
+
+use skippable_map::{SkipReport, SkippableDeserialize, SkippableMap, SkippablePartitions};
+
+#[derive(Debug, SkippableDeserialize)]
+struct Settings<T> {
+    level: T,
+    #[skippable(default = vec![String::from("default")])]
+    names: Vec<String>,
+    limit: Option<u64>,
+    #[skippable(report)]
+    skipped: SkipReport<String>,
+}
+
+#[test]
+fn conforming_fields_are_kept() {
+    let json = r#"{"level": 2, "names": ["a", "b"], "limit": 10, "other": [1]}"#;
+    let settings: Settings<u8> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(settings.level, 2);
+    assert_eq!(settings.names, ["a", "b"]);
+    assert_eq!(settings.limit, Some(10));
+    assert!(settings.skipped.is_empty());
+}
+
+#[test]
+fn non_conforming_fields_fall_back() {
+    let json = r#"{"limit": "x", "other": 1, "level": 300, "names": "a"}"#;
+    let settings: Settings<u8> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(settings.level, 0);
+    assert_eq!(settings.names, ["default"]);
+    assert_eq!(settings.limit, None);
+
+    let skipped: Vec<_> = settings
+        .skipped
+        .iter()
+        .map(|entry| (entry.index, entry.key.as_deref()))
+        .collect();
+    assert_eq!(
+        skipped,
+        [(2, Some("level")), (3, Some("names")), (0, Some("limit"))]
+    );
+}
+
+#[test]
+fn missing_fields_fall_back() {
+    let settings: Settings<String> = serde_yaml::from_str("names: [a]").unwrap();
+
+    assert_eq!(settings.level, "");
+    assert_eq!(settings.limit, None);
+    assert_eq!(settings.skipped.len(), 1);
+    assert_eq!(settings.skipped.0[0].index, 1);
+    assert!(settings.skipped.0[0]
+        .error
+        .message()
+        .contains("missing field `level`"));
+}
+
+#[derive(Debug, SkippablePartitions)]
+struct ByType<T> {
+    numbers: SkippableMap<String, T>,
+    strings: SkippableMap<String, String>,
+}
+
+#[derive(Debug, SkippablePartitions)]
+struct Unnamed(SkippableMap<String, bool>, SkippableMap<String, u64>);
+
+#[test]
+fn struct_partitions() {
+    let json = r#"{"a": 1, "b": "x", "c": true, "d": 2}"#;
+    let by_type: ByType<u8> = serde_json::from_str(json).unwrap();
+    assert_eq!(by_type.numbers.0.len(), 2);
+    assert_eq!(by_type.strings.0["b"], "x");
+
+    let unnamed: Unnamed = serde_json::from_str(json).unwrap();
+    assert_eq!(unnamed.0 .0.len(), 1);
+    assert_eq!(unnamed.1 .0.len(), 2);
+}
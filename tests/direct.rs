@@ -0,0 +1,122 @@
+#[cfg(any(feature = "bincode", feature = "postcard"))]
+use skippable_map::{SkipReport, SkippableMapSeed};
+#[cfg(any(feature = "bincode", feature = "postcard"))]
+use std::collections::HashMap;
+
+/// A map whose second value is not valid UTF-8, as strings and byte vectors have the same layout
+#[cfg(any(feature = "bincode", feature = "postcard"))]
+fn invalid_utf8() -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        ("a", b"x".to_vec()),
+        ("b", vec![0xff]),
+        ("c", b"y".to_vec()),
+    ]
+}
+
+/// A map whose second value is not a valid bool
+#[cfg(any(feature = "bincode", feature = "postcard"))]
+fn invalid_bool() -> Vec<(&'static str, u8)> {
+    vec![("a", 1), ("b", 2), ("c", 0)]
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn bincode_conforming_maps_are_read() {
+    use bincode::Options;
+
+    let input = HashMap::from([("a".to_string(), 1u64), ("b".to_string(), 2)]);
+    let bytes = bincode::options().serialize(&input).unwrap();
+    let map = SkippableMapSeed::<String, u64>::new()
+        .deserialize_recoverable(&mut bincode::Deserializer::from_slice(
+            &bytes,
+            bincode::options(),
+        ))
+        .unwrap();
+    assert_eq!(map.inner(), input);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn bincode_invalid_values_are_skipped() {
+    use bincode::Options;
+
+    let bytes = bincode::options().serialize(&invalid_utf8()).unwrap();
+    let mut report = SkipReport::default();
+    let map = SkippableMapSeed::<String, String>::new()
+        .collect_skipped(&mut report)
+        .deserialize_recoverable(&mut bincode::Deserializer::from_slice(
+            &bytes,
+            bincode::options(),
+        ))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert_eq!(map.0["c"], "y");
+    assert_eq!(report.0[0].key.as_deref(), Some("b"));
+
+    let bytes = bincode::options().serialize(&invalid_bool()).unwrap();
+    let map = SkippableMapSeed::<String, bool>::new()
+        .deserialize_recoverable(&mut bincode::Deserializer::from_slice(
+            &bytes,
+            bincode::options(),
+        ))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert!(!map.0["c"]);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn bincode_truncated_input_is_an_error() {
+    use bincode::Options;
+
+    let bytes = bincode::options().serialize(&invalid_utf8()).unwrap();
+    for len in 0..bytes.len() {
+        let result = SkippableMapSeed::<String, String>::new().deserialize_recoverable(
+            &mut bincode::Deserializer::from_slice(&bytes[..len], bincode::options()),
+        );
+        assert!(result.is_err(), "{len}");
+    }
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_conforming_maps_are_read() {
+    let input = HashMap::from([("a".to_string(), 1u64), ("b".to_string(), 2)]);
+    let bytes = postcard::to_allocvec(&input).unwrap();
+    let map = SkippableMapSeed::<String, u64>::new()
+        .deserialize_recoverable(&mut postcard::Deserializer::from_bytes(&bytes))
+        .unwrap();
+    assert_eq!(map.inner(), input);
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_invalid_values_are_skipped() {
+    let bytes = postcard::to_allocvec(&invalid_utf8()).unwrap();
+    let mut report = SkipReport::default();
+    let map = SkippableMapSeed::<String, String>::new()
+        .collect_skipped(&mut report)
+        .deserialize_recoverable(&mut postcard::Deserializer::from_bytes(&bytes))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert_eq!(map.0["c"], "y");
+    assert_eq!(report.0[0].key.as_deref(), Some("b"));
+
+    let bytes = postcard::to_allocvec(&invalid_bool()).unwrap();
+    let map = SkippableMapSeed::<String, bool>::new()
+        .deserialize_recoverable(&mut postcard::Deserializer::from_bytes(&bytes))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert!(!map.0["c"]);
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_truncated_input_is_an_error() {
+    let bytes = postcard::to_allocvec(&invalid_utf8()).unwrap();
+    for len in 0..bytes.len() {
+        let result = SkippableMapSeed::<String, String>::new()
+            .deserialize_recoverable(&mut postcard::Deserializer::from_bytes(&bytes[..len]));
+        assert!(result.is_err(), "{len}");
+    }
+}
@@ -0,0 +1,130 @@
+use skippable_map::{
+    CollectAll, DenyDuplicates, FirstWins, LastWins, SkippableMap, SkippableMapWithExtras,
+    SkippableMapWithReport,
+};
+use std::collections::HashMap;
+
+type Map<P> = SkippableMap<String, u64, HashMap<String, u64>, P>;
+
+const DUPLICATES: &str = r#"{"a": 1, "b": 2, "a": "x", "a": 3, "c": -1, "c": 4}"#;
+
+#[test]
+fn last_wins_is_the_default() {
+    let map: SkippableMap<String, u64> = serde_json::from_str(DUPLICATES).unwrap();
+    let last_wins: Map<LastWins> = serde_json::from_str(DUPLICATES).unwrap();
+
+    assert_eq!(map.0, last_wins.0);
+}
+
+#[test]
+fn last_wins() {
+    let map: Map<LastWins> = serde_json::from_str(DUPLICATES).unwrap();
+
+    assert_eq!(map.0["a"], 3);
+    assert_eq!(map.0["b"], 2);
+    assert_eq!(map.0["c"], 4);
+}
+
+#[test]
+fn first_wins() {
+    let map: Map<FirstWins> = serde_json::from_str(DUPLICATES).unwrap();
+
+    assert_eq!(map.0["a"], 1);
+    assert_eq!(map.0["b"], 2);
+    // The skipped -1 is not the first occurrence of "c"
+    assert_eq!(map.0["c"], 4);
+}
+
+#[test]
+fn deny_duplicates() {
+    let err = serde_json::from_str::<Map<DenyDuplicates>>(DUPLICATES).unwrap_err();
+    assert!(
+        err.to_string().contains("duplicate key at entry 3"),
+        "{err}"
+    );
+
+    // Skipped entries do not count as occurrences of their key
+    let json = r#"{"a": "x", "a": 1, "b": 2, "b": -1}"#;
+    let map: Map<DenyDuplicates> = serde_json::from_str(json).unwrap();
+    assert_eq!(map.0["a"], 1);
+    assert_eq!(map.0["b"], 2);
+}
+
+#[test]
+fn collect_all() {
+    let map: SkippableMap<String, u64, HashMap<String, Vec<u64>>, CollectAll> =
+        serde_json::from_str(DUPLICATES).unwrap();
+
+    assert_eq!(map.0["a"], vec![1, 3]);
+    assert_eq!(map.0["b"], vec![2]);
+    assert_eq!(map.0["c"], vec![4]);
+}
+
+#[test]
+fn into_map_with_any_policy() {
+    let first: Map<FirstWins> = serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(HashMap::from(first)["a"], 1);
+
+    let all: SkippableMap<String, u64, HashMap<String, Vec<u64>>, CollectAll> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    let all: HashMap<String, Vec<u64>> = all.into();
+    assert_eq!(all["a"], vec![1, 3]);
+}
+
+#[test]
+fn policies_with_a_report() {
+    let first: SkippableMapWithReport<String, u64, HashMap<String, u64>, FirstWins> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(first.map.0["a"], 1);
+    assert_eq!(first.report.len(), 2);
+
+    let err = serde_json::from_str::<
+        SkippableMapWithReport<String, u64, HashMap<String, u64>, DenyDuplicates>,
+    >(DUPLICATES)
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("duplicate key at entry 3"),
+        "{err}"
+    );
+}
+
+#[test]
+fn policies_with_extras() {
+    let first: SkippableMapWithExtras<String, u64, HashMap<String, u64>, FirstWins> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(first.map.0["a"], 1);
+    assert_eq!(first.extras_len(), 2);
+
+    let all: SkippableMapWithExtras<String, u64, HashMap<String, Vec<u64>>, CollectAll> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(all.map.0["a"], vec![1, 3]);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn policies_with_the_rest() {
+    use skippable_map::SkippableMapWithRest;
+
+    let first: SkippableMapWithRest<String, u64, HashMap<String, u64>, FirstWins> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(first.map.0["a"], 1);
+    assert_eq!(first.rest()["c"], -1);
+
+    let result: Result<SkippableMapWithRest<String, u64, HashMap<String, u64>, DenyDuplicates>, _> =
+        serde_json::from_str(DUPLICATES);
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "btreemap")]
+#[test]
+fn policies_with_other_maps() {
+    use std::collections::BTreeMap;
+
+    let first: SkippableMap<String, u64, BTreeMap<String, u64>, FirstWins> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(BTreeMap::from(first)["a"], 1);
+
+    let all: SkippableMap<String, u64, BTreeMap<String, Vec<u64>>, CollectAll> =
+        serde_json::from_str(DUPLICATES).unwrap();
+    assert_eq!(BTreeMap::from(all)["a"], vec![1, 3]);
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "json")]
+
+use skippable_map::{JsonEntries, JsonEntriesError};
+use std::io::{self, Read};
+
+fn entries<K, V>(json: &str) -> Vec<Result<(K, V), JsonEntriesError>>
+where
+    K: serde::de::DeserializeOwned,
+    V: serde::de::DeserializeOwned,
+{
+    JsonEntries::new(json.as_bytes()).collect()
+}
+
+#[test]
+fn conforming_entries_are_yielded_in_order() {
+    let json = r#" {
+        "a": 1, "b": "x", "c": [1, {"d": "}]\"["}], "e": -1, "f": 2.5,
+        "g\"h": 3, "i": {"j": [[]]}, "a": 4
+    } "#;
+    let entries: Vec<(String, u64)> = entries(json).into_iter().map(Result::unwrap).collect();
+    assert_eq!(
+        entries,
+        [
+            ("a".to_string(), 1),
+            ("g\"h".to_string(), 3),
+            ("a".to_string(), 4)
+        ]
+    );
+}
+
+#[test]
+fn structured_values_and_parsed_keys() {
+    let json = r#"{"1": [1, 2], "x": [3], "2": [4, "y"], "3": []}"#;
+    let entries: Vec<(u32, Vec<u64>)> = entries(json).into_iter().map(Result::unwrap).collect();
+    assert_eq!(entries, [(1, vec![1, 2]), (3, vec![])]);
+}
+
+#[test]
+fn empty_object() {
+    assert!(entries::<String, u64>(" {} ").is_empty());
+}
+
+#[test]
+fn broken_input_ends_iteration_with_an_error() {
+    for json in [
+        "",
+        "[]",
+        r#"{"a": 1"#,
+        r#"{"a": 1,"#,
+        r#"{"a": 1,}"#,
+        r#"{"a": 1 "b": 2}"#,
+        r#"{"a" 1}"#,
+        r#"{1: 1}"#,
+        r#"{"a": [1,, 2]}"#,
+        r#"{"a": tru}"#,
+        r#"{"a": "x}"#,
+        r#"{"a": 1} x"#,
+    ] {
+        let results = entries::<String, u64>(json);
+        assert!(
+            results.last().is_some_and(Result::is_err),
+            "{json}: {results:?}"
+        );
+    }
+
+    // Entries before the error are still yielded
+    let results = entries::<String, u64>(r#"{"a": 1, "b": "x", "c": 2,, "d": 3}"#);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &("a".to_string(), 1));
+    assert_eq!(results[1].as_ref().unwrap(), &("c".to_string(), 2));
+    assert!(results[2].is_err());
+}
+
+#[test]
+fn errors_give_their_position() {
+    let json = r#"{"a": 1 "b": 2}"#;
+    let err = entries::<String, u64>(json).pop().unwrap().unwrap_err();
+    assert!(
+        matches!(err, JsonEntriesError::Syntax { offset: 8, .. }),
+        "{err:?}"
+    );
+    assert!(err.to_string().contains("at byte 8"), "{err}");
+
+    let err = entries::<String, u64>(r#"{"a": 1,"#)
+        .pop()
+        .unwrap()
+        .unwrap_err();
+    assert!(
+        matches!(err, JsonEntriesError::Syntax { offset: 8, .. }),
+        "{err:?}"
+    );
+
+    // Errors within a value are positioned relative to its start
+    let json = r#"{"a": 1, "b": [1,, 2]}"#;
+    let mut iter = JsonEntries::<_, String, u64>::new(json.as_bytes());
+    assert!(iter.next().unwrap().is_ok());
+    let err = iter.next().unwrap().unwrap_err();
+    let JsonEntriesError::Json { error, offset } = &err else {
+        panic!("{err:?}");
+    };
+    assert!(error.is_syntax());
+    assert_eq!((error.line(), error.column()), (1, 4));
+    assert_eq!(*offset, 14);
+    assert_eq!(err.offset(), Some(iter.offset()));
+}
+
+/// A reader which fails after `remaining` bytes
+struct Failing<'a> {
+    data: &'a [u8],
+    remaining: usize,
+}
+
+impl Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(io::Error::other("failed"));
+        }
+        let n = buf.len().min(self.remaining).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        self.remaining -= n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn io_errors_are_returned() {
+    let reader = Failing {
+        data: br#"{"a": 1, "b": 2}"#,
+        remaining: 10,
+    };
+    let results: Vec<Result<(String, u64), _>> = JsonEntries::new(reader).collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    let err = results[1].as_ref().unwrap_err();
+    assert!(matches!(err, JsonEntriesError::Io(_)), "{err:?}");
+    assert_eq!(err.offset(), None);
+}
+
+/// A reader of an object which never ends, with alternating conforming entries
+struct Endless {
+    next: usize,
+    pending: Vec<u8>,
+}
+
+impl Read for Endless {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pending.is_empty() {
+            let i = self.next;
+            self.next += 1;
+            let value = if i % 2 == 1 { r#""x""# } else { "1" };
+            let separator = if i == 0 { "{" } else { "," };
+            self.pending = format!(r#"{separator}"{i}": {value}"#).into_bytes();
+        }
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+}
+
+#[test]
+fn entries_are_yielded_as_they_are_read() {
+    let reader = Endless {
+        next: 0,
+        pending: Vec::new(),
+    };
+    let keys: Vec<u64> = JsonEntries::<_, u64, u8>::new(reader)
+        .take(1000)
+        .map(|entry| entry.unwrap().0)
+        .collect();
+    assert_eq!(keys, (0..2000).step_by(2).collect::<Vec<_>>());
+}
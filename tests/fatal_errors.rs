@@ -0,0 +1,140 @@
+use skippable_map::SkippableMap;
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+};
+
+type Numbers = SkippableMap<String, u64>;
+
+fn numbers(entries: &[(&str, u64)]) -> HashMap<String, u64> {
+    entries.iter().map(|&(k, v)| (k.to_string(), v)).collect()
+}
+
+#[test]
+fn json_wrong_types_are_skipped() {
+    let json = r#"{"a": "x", "b": 2, "c": [1, {"d": true}], "e": -1, "f": 6}"#;
+    let map: Numbers = serde_json::from_str(json).unwrap();
+    assert_eq!(map.inner(), numbers(&[("b", 2), ("f", 6)]));
+}
+
+#[test]
+fn json_truncated_is_an_error() {
+    for json in [
+        r#"{"a": 1, "b": "#,
+        r#"{"a": 1, "b"#,
+        r#"{"a": 1, "b": [1, 2"#,
+        r#"{"a": 1, "b": "unterminated"#,
+        r#"{"a": 1,"#,
+        r#"{"a": 1"#,
+    ] {
+        assert!(serde_json::from_str::<Numbers>(json).is_err(), "{json}");
+    }
+}
+
+#[test]
+fn json_malformed_is_an_error() {
+    for json in [
+        r#"{"a": 1 "b": 2}"#,
+        r#"{"a": 1,, "b": 2}"#,
+        r#"{"a": , "b": 2}"#,
+        r#"{"a": tru, "b": 2}"#,
+        r#"{"a": [1 2], "b": 2}"#,
+        r#"{"a" 1, "b": 2}"#,
+        r#"{"a": 1, b: 2}"#,
+        r#"{"a": 1}}"#,
+    ] {
+        assert!(serde_json::from_str::<Numbers>(json).is_err(), "{json}");
+    }
+}
+
+/// Yields the bytes of `data`, then fails every subsequent read
+struct FailingReader<'a> {
+    data: &'a [u8],
+}
+
+impl Read for FailingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.data.is_empty() {
+            return Err(io::Error::other("connection reset"));
+        }
+        self.data.read(buf)
+    }
+}
+
+#[test]
+fn json_reader_io_error_is_an_error() {
+    for data in [r#"{"a": 1, "b": "#, r#"{"a": 1, "b"#, r#"{"a": "x", "#] {
+        let reader = FailingReader {
+            data: data.as_bytes(),
+        };
+        let err = serde_json::from_reader::<_, Numbers>(reader).unwrap_err();
+        assert!(err.is_io(), "{data}: {err}");
+    }
+}
+
+#[test]
+fn yaml_wrong_types_are_skipped() {
+    let yaml = "a: x\nb: 2\nc: [1, 2]\nd: {e: 1}\nf: 6\n";
+    let map: Numbers = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(map.inner(), numbers(&[("b", 2), ("f", 6)]));
+}
+
+#[test]
+fn yaml_truncated_or_malformed_is_an_error() {
+    for yaml in [
+        "a: 1\nb: [1, 2\n",
+        "a: 1\nb: {c: 1\n",
+        "a: 1\nb: \"unterminated\n",
+        "a: 1\n b: 2\n- c\n",
+        "a: 1\nb: 2: 3\n",
+    ] {
+        assert!(serde_yaml::from_str::<Numbers>(yaml).is_err(), "{yaml}");
+    }
+}
+
+#[test]
+fn toml_wrong_types_are_skipped() {
+    let toml = "a = \"x\"\nb = 2\nc = [1, 2]\nf = 6\n\n[d]\ne = 1\n";
+    let map: Numbers = toml::from_str(toml).unwrap();
+    assert_eq!(map.inner(), numbers(&[("b", 2), ("f", 6)]));
+}
+
+#[test]
+fn toml_truncated_or_malformed_is_an_error() {
+    for toml in [
+        "a = 1\nb = ",
+        "a = 1\nb = [1, 2\n",
+        "a = 1\nb = \"unterminated\n",
+        "a = 1\nb 2\n",
+        "a = 1\n[c\nd = 2\n",
+    ] {
+        assert!(toml::from_str::<Numbers>(toml).is_err(), "{toml}");
+    }
+}
+
+#[test]
+fn yaml_out_of_range_integers_are_skipped() {
+    let yaml = "a: 1\nb: 99999999999999999999\nc: 2\nd: -99999999999999999999\n";
+    let map: Numbers = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(map.inner(), numbers(&[("a", 1), ("c", 2)]));
+
+    let map: SkippableMap<String, i128> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(map.0["b"], 99999999999999999999);
+    assert_eq!(map.0["d"], -99999999999999999999);
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn cbor_bignums_are_skipped() {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(
+        &std::collections::BTreeMap::from([("a", 1u128), ("b", u128::MAX), ("c", 2)]),
+        &mut cbor,
+    )
+    .unwrap();
+    let map: Numbers = ciborium::from_reader(cbor.as_slice()).unwrap();
+    assert_eq!(map.inner(), numbers(&[("a", 1), ("c", 2)]));
+
+    let map: SkippableMap<String, u128> = ciborium::from_reader(cbor.as_slice()).unwrap();
+    assert_eq!(map.0["b"], u128::MAX);
+}
@@ -0,0 +1,170 @@
+use serde::de::DeserializeSeed;
+use skippable_map::{for_each_conforming, ExtendSeed};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::Read,
+    ops::ControlFlow,
+};
+
+#[test]
+fn entries_are_visited_in_order() {
+    let json = r#"{"a": 1, "b": "x", "c": 2, "d": -1, "c": 3}"#;
+    let mut seen = Vec::new();
+    let flow = for_each_conforming::<String, u64>(
+        &mut serde_json::Deserializer::from_str(json),
+        |k, v| {
+            seen.push((k, v));
+            ControlFlow::Continue(())
+        },
+    )
+    .unwrap();
+    assert!(flow.is_continue());
+
+    assert_eq!(
+        seen,
+        [
+            ("a".to_string(), 1),
+            ("c".to_string(), 2),
+            ("c".to_string(), 3)
+        ]
+    );
+}
+
+#[test]
+fn sequences_are_rejected() {
+    for json in [
+        r#"[["a", 1], ["b", "x"], {"key": "c", "value": 2}, 3]"#,
+        "[]",
+    ] {
+        let result = for_each_conforming::<String, u64>(
+            &mut serde_json::Deserializer::from_str(json),
+            |_, _| panic!("no entries are expected"),
+        );
+        assert!(result.is_err(), "{json}");
+    }
+}
+
+#[test]
+fn reader_input() {
+    // Entries are handled as they are read from an io::Read
+    let json = format!(
+        "{{{}}}",
+        (0..1000)
+            .map(|i| format!(r#""{i}": {}"#, if i % 2 == 0 { "1" } else { "\"x\"" }))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let mut count = 0;
+    let flow = for_each_conforming::<u32, u8>(
+        &mut serde_json::Deserializer::from_reader(json.as_bytes()),
+        |_, _| {
+            count += 1;
+            ControlFlow::Continue(())
+        },
+    )
+    .unwrap();
+    assert!(flow.is_continue());
+    assert_eq!(count, 500);
+}
+
+/// Counts the bytes read through it
+struct Counting<R>(R, usize);
+
+impl<R: Read> Read for Counting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.0.read(buf)?;
+        self.1 += n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn breaking_stops_reading() {
+    // The rest of the input is not read, so is not found to be broken
+    let json = r#"{"a": 1, "b": "x", "c": 2, "d": 3,, "#;
+    let mut reader = Counting(json.as_bytes(), 0);
+    let mut seen = Vec::new();
+    let mut found = None;
+    let flow = for_each_conforming::<String, u64>(
+        &mut serde_json::Deserializer::from_reader(&mut reader),
+        |k, v| {
+            seen.push(v);
+            if v == 2 {
+                found = Some(k);
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    )
+    .unwrap();
+
+    assert!(flow.is_break());
+    assert_eq!(found.as_deref(), Some("c"));
+    assert_eq!(seen, [1, 2]);
+    assert!(reader.1 < json.len(), "{} bytes read", reader.1);
+}
+
+#[test]
+fn breaking_with_an_error() {
+    let json = r#"{"a": 1, "b": 2000}"#;
+    let mut error = None;
+    let flow = for_each_conforming::<String, u64>(
+        &mut serde_json::Deserializer::from_str(json),
+        |k, v| {
+            if v > 1000 {
+                error = Some(format!("{k} is too large"));
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        },
+    );
+    assert!(flow.unwrap().is_break());
+    assert_eq!(error.as_deref(), Some("b is too large"));
+}
+
+#[test]
+fn extend_existing_collections() {
+    let mut map = HashMap::from([("z".to_string(), 0)]);
+    ExtendSeed::new(&mut map)
+        .deserialize(&mut serde_json::Deserializer::from_str(
+            r#"{"a": 1, "b": [], "z": 2}"#,
+        ))
+        .unwrap();
+    assert_eq!(
+        map,
+        HashMap::from([("a".to_string(), 1), ("z".to_string(), 2)])
+    );
+
+    let mut set = BTreeSet::new();
+    ExtendSeed::new(&mut set)
+        .deserialize(serde_yaml::Deserializer::from_str("1: a\nx: b\n2: c\n"))
+        .unwrap();
+    assert_eq!(
+        set,
+        BTreeSet::from([(1u8, "a".to_string()), (2, "c".to_string())])
+    );
+}
+
+#[test]
+fn broken_input_is_an_error_after_earlier_entries() {
+    let json = r#"{"a": 1, "b": 2,, "c": 3}"#;
+    let mut seen = Vec::new();
+    let result =
+        ExtendSeed::new(&mut seen).deserialize(&mut serde_json::Deserializer::from_str(json));
+
+    assert!(result.is_err());
+    assert_eq!(seen, [("a".to_string(), 1u64), ("b".to_string(), 2)]);
+}
+
+#[test]
+fn non_map_input_is_an_error() {
+    let result =
+        for_each_conforming::<String, u64>(&mut serde_json::Deserializer::from_str("1"), |_, _| {
+            ControlFlow::Continue(())
+        });
+    // The deserializer's own error is boxed
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<serde_json::Error>().unwrap().is_data());
+}
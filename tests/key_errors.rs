@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use skippable_map::{SkippableMap, SkippableMapWithReport};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Deserialize, Hash, PartialEq, Eq)]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn numbers(entries: &[(u32, u64)]) -> HashMap<u32, u64> {
+    entries.iter().copied().collect()
+}
+
+fn colours(entries: &[(Colour, u64)]) -> HashMap<Colour, u64> {
+    entries.iter().copied().collect()
+}
+
+#[test]
+fn json_integer_keys() {
+    let json = r#"{"1": 1, "x": 2, "-3": 3, "4": {"nested": [5, 6]}, "7": 7}"#;
+    let map: SkippableMap<u32, u64> = serde_json::from_str(json).unwrap();
+    assert_eq!(map.inner(), numbers(&[(1, 1), (7, 7)]));
+}
+
+#[test]
+fn json_enum_keys() {
+    let json = r#"{"Red": 1, "Blue": {"a": [1, 2]}, "Green": 2}"#;
+    let map: SkippableMap<Colour, u64> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        map.inner(),
+        colours(&[(Colour::Red, 1), (Colour::Green, 2)])
+    );
+}
+
+#[test]
+fn yaml_integer_keys() {
+    let yaml = "1: 1\nx: 2\n[1, 2]: 3\n{a: b}: 4\n-5: 5\n6: 6\n";
+    let map: SkippableMap<u32, u64> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(map.inner(), numbers(&[(1, 1), (6, 6)]));
+}
+
+#[test]
+fn yaml_enum_keys() {
+    let yaml = "Red: 1\nBlue:\n  - 1\n  - 2\n[Green]: 3\nGreen: 2\n";
+    let map: SkippableMap<Colour, u64> = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        map.inner(),
+        colours(&[(Colour::Red, 1), (Colour::Green, 2)])
+    );
+}
+
+#[test]
+fn toml_integer_keys() {
+    let toml = "1 = 1\nx = 2\n3 = { a = [1, 2] }\n4 = 4\n\n[5]\nb = 1\n";
+    let map: SkippableMap<u32, u64> = toml::from_str(toml).unwrap();
+    assert_eq!(map.inner(), numbers(&[(1, 1), (4, 4)]));
+}
+
+#[test]
+fn toml_enum_keys() {
+    let toml = "Red = 1\nBlue = [1, 2]\nGreen = 2\n\n[Yellow]\na = 1\n";
+    let map: SkippableMap<Colour, u64> = toml::from_str(toml).unwrap();
+    assert_eq!(
+        map.inner(),
+        colours(&[(Colour::Red, 1), (Colour::Green, 2)])
+    );
+}
+
+#[test]
+fn skipped_keys_are_reported() {
+    let json = r#"{"1": 1, "x": [2], "3": "y", "4": 4}"#;
+    let map: SkippableMapWithReport<u32, u64> = serde_json::from_str(json).unwrap();
+    let (map, report) = map.into_parts();
+    assert_eq!(map.inner(), numbers(&[(1, 1), (4, 4)]));
+
+    let skipped: Vec<_> = report
+        .iter()
+        .map(|entry| (entry.index, entry.key))
+        .collect();
+    assert_eq!(skipped, [(1, None), (2, Some(3))]);
+}
@@ -0,0 +1,35 @@
+use skippable_map::Nested;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn only_the_smallest_enclosing_element_is_skipped() {
+    let json = r#"{"a": [[1, "x", 2], "y", [3]], "b": {"c": [4, -5]}, "d": [[6]]}"#;
+    let value: Nested<HashMap<String, Vec<Vec<u64>>>> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(value.0["a"], vec![vec![1, 2], vec![3]]);
+    assert!(!value.0.contains_key("b"));
+    assert_eq!(value.0["d"], vec![vec![6]]);
+}
+
+#[test]
+fn options_become_none() {
+    let json = r#"[1, null, "x", 2]"#;
+    let value: Nested<Vec<Option<u64>>> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(value.0, [Some(1), None, None, Some(2)]);
+}
+
+#[test]
+fn yaml_nested_maps() {
+    let yaml = "a:\n  x: 1\n  y: [1]\nb: 2\nc:\n  z: 3\n";
+    let value: Nested<BTreeMap<String, BTreeMap<String, u64>>> =
+        serde_yaml::from_str(yaml).unwrap();
+
+    assert_eq!(
+        value.0,
+        BTreeMap::from([
+            (String::from("a"), BTreeMap::from([(String::from("x"), 1)])),
+            (String::from("c"), BTreeMap::from([(String::from("z"), 3)])),
+        ])
+    );
+}
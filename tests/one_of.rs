@@ -0,0 +1,71 @@
+use serde::{Deserialize, Deserializer};
+use skippable_map::{OneOf, SkippableMap};
+use std::collections::HashMap;
+
+/// A number written as a string
+struct Parsed(u64);
+
+impl<'de> Deserialize<'de> for Parsed {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map(Parsed).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<Parsed> for u64 {
+    fn from(value: Parsed) -> Self {
+        value.0
+    }
+}
+
+#[test]
+fn number_or_parsed_string() {
+    let json = r#"{"a": 1, "b": "2", "c": "x", "d": -1, "e": [3]}"#;
+    let map: SkippableMap<String, OneOf<u64, Parsed>> = serde_json::from_str(json).unwrap();
+    let numbers: HashMap<String, u64> = map
+        .inner()
+        .into_iter()
+        .map(|(k, v)| (k, v.unify()))
+        .collect();
+
+    assert_eq!(
+        numbers,
+        HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+    );
+}
+
+#[test]
+fn nested_alternatives_are_tried_in_order() {
+    let json = r#"{"a": 1, "b": true, "c": "x", "d": null}"#;
+    let map: SkippableMap<String, OneOf<u64, OneOf<bool, String>>> =
+        serde_json::from_str(json).unwrap();
+
+    assert_eq!(map.0["a"], OneOf::First(1));
+    assert_eq!(map.0["b"], OneOf::Second(OneOf::First(true)));
+    assert_eq!(map.0["c"], OneOf::Second(OneOf::Second("x".to_string())));
+    assert_eq!(map.0.len(), 3);
+}
+
+#[test]
+fn first_match_wins() {
+    let value: OneOf<i64, u64> = serde_json::from_str("1").unwrap();
+    assert_eq!(value, OneOf::First(1));
+    assert_eq!(value.second(), None);
+}
+
+#[test]
+fn no_match_is_an_error() {
+    let error = serde_json::from_str::<OneOf<u64, bool>>(r#""x""#).unwrap_err();
+    assert!(error.to_string().contains("did not match u64 or bool"));
+}
+
+#[test]
+fn round_trip() {
+    for json in ["1", r#""x""#] {
+        let value: OneOf<u64, String> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+    }
+}
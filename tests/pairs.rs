@@ -0,0 +1,70 @@
+use serde::de::DeserializeSeed;
+use skippable_map::{PairShape, SkipReport, SkippableMap, SkippableMapSeed};
+use std::collections::HashMap;
+
+fn numbers(entries: &[(&str, u64)]) -> HashMap<String, u64> {
+    entries.iter().map(|&(k, v)| (k.to_string(), v)).collect()
+}
+
+fn common<'de, D, K>(deserializer: D) -> Result<SkippableMap<K, u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    K: serde::Deserialize<'de> + std::hash::Hash + Eq,
+{
+    SkippableMapSeed::new()
+        .pair_shapes(PairShape::COMMON)
+        .deserialize(deserializer)
+}
+
+#[test]
+fn json_tuples_and_objects() {
+    let json = r#"[
+        ["a", 1],
+        ["b", "x"],
+        ["c", 3, 4],
+        {"key": "d", "value": 4, "extra": true},
+        {"key": "e"},
+        "f",
+        ["g", 7]
+    ]"#;
+    let map = common(&mut serde_json::Deserializer::from_str(json)).unwrap();
+    assert_eq!(map.inner(), numbers(&[("a", 1), ("d", 4), ("g", 7)]));
+}
+
+#[test]
+fn yaml_tuples_with_integer_keys() {
+    let yaml = "- [1, 10]\n- [x, 20]\n- key: 3\n  value: 30\n";
+    let map = common(serde_yaml::Deserializer::from_str(yaml)).unwrap();
+    assert_eq!(map.inner(), HashMap::from([(1, 10), (3, 30)]));
+}
+
+#[test]
+fn custom_shapes() {
+    let json = r#"[["a", 1], {"name": "b", "count": 2}, {"key": "c", "value": 3}]"#;
+    let shapes = [PairShape::Object {
+        key: "name",
+        value: "count",
+    }];
+    let mut report = SkipReport::default();
+    let map = SkippableMapSeed::<String, u64>::new()
+        .pair_shapes(&shapes)
+        .collect_skipped(&mut report)
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+        .unwrap();
+
+    assert_eq!(map.inner(), numbers(&[("b", 2)]));
+    let skipped: Vec<_> = report.iter().map(|entry| entry.index).collect();
+    assert_eq!(skipped, [0, 2]);
+}
+
+#[test]
+fn sequences_are_rejected_by_default() {
+    for json in [r#"[["a", 1]]"#, "[1, 2, 3]", "[]"] {
+        let result = serde_json::from_str::<SkippableMap<String, u64>>(json);
+        assert!(result.is_err(), "{json}");
+
+        let result = SkippableMapSeed::<String, u64>::new()
+            .deserialize(&mut serde_json::Deserializer::from_str(json));
+        assert!(result.is_err(), "{json}");
+    }
+}
@@ -0,0 +1,86 @@
+use skippable_map::{DenyDuplicates, Partitioned, SkippableMap};
+use std::collections::HashMap;
+
+type ByType = (
+    SkippableMap<String, u64>,
+    SkippableMap<String, String>,
+    SkippableMap<String, Vec<u64>>,
+);
+
+#[test]
+fn entries_go_to_the_first_partition_they_conform_to() {
+    let json = r#"{"a": 1, "b": "x", "c": [1, 2], "d": -1, "e": 2, "f": ["y"]}"#;
+    let (numbers, strings, lists) = serde_json::from_str::<Partitioned<ByType>>(json)
+        .unwrap()
+        .inner();
+
+    assert_eq!(
+        numbers.inner(),
+        HashMap::from([("a".to_string(), 1), ("e".to_string(), 2)])
+    );
+    assert_eq!(
+        strings.inner(),
+        HashMap::from([("b".to_string(), "x".to_string())])
+    );
+    assert_eq!(
+        lists.inner(),
+        HashMap::from([("c".to_string(), vec![1, 2])])
+    );
+}
+
+#[test]
+fn earlier_partitions_take_priority() {
+    // Every number is also valid as an i64, but goes to the u64 partition if it can
+    let json = r#"{"a": 1, "b": -1}"#;
+    let Partitioned((unsigned, signed)): Partitioned<(
+        SkippableMap<String, u64>,
+        SkippableMap<String, i64>,
+    )> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(unsigned.inner(), HashMap::from([("a".to_string(), 1)]));
+    assert_eq!(signed.inner(), HashMap::from([("b".to_string(), -1)]));
+}
+
+#[test]
+fn partitions_by_key_type() {
+    let yaml = "1: a\nx: b\n2: c\n";
+    let Partitioned((numbered, named)): Partitioned<(
+        SkippableMap<u32, String>,
+        SkippableMap<String, String>,
+    )> = serde_yaml::from_str(yaml).unwrap();
+
+    assert_eq!(
+        numbered.inner(),
+        HashMap::from([(1, "a".to_string()), (2, "c".to_string())])
+    );
+    assert_eq!(
+        named.inner(),
+        HashMap::from([("x".to_string(), "b".to_string())])
+    );
+}
+
+#[test]
+fn sequences_are_rejected() {
+    for json in [r#"[["a", 1], {"key": "b", "value": "x"}, 3]"#, "[]"] {
+        let result = serde_json::from_str::<
+            Partitioned<(SkippableMap<String, u64>, SkippableMap<String, String>)>,
+        >(json);
+        assert!(result.is_err(), "{json}");
+    }
+}
+
+#[test]
+fn duplicate_policy_is_applied() {
+    let json = r#"{"a": 1, "b": "x", "a": 2}"#;
+    let result = serde_json::from_str::<
+        Partitioned<(
+            SkippableMap<String, u64, HashMap<String, u64>, DenyDuplicates>,
+            SkippableMap<String, String>,
+        )>,
+    >(json);
+    let err = result.unwrap_err();
+    assert!(
+        err.to_string().contains("duplicate key at entry 2"),
+        "{err}"
+    );
+}
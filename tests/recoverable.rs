@@ -0,0 +1,150 @@
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+use skippable_map::Recoverable;
+#[cfg(feature = "json")]
+use skippable_map::SkipReport;
+#[cfg(any(feature = "json", feature = "msgpack"))]
+use skippable_map::SkippableMapSeed;
+
+/// Fails validation with a custom error if the number is odd
+#[cfg(any(feature = "json", feature = "msgpack"))]
+#[derive(Debug)]
+struct Even(u64);
+
+#[cfg(any(feature = "json", feature = "msgpack"))]
+impl<'de> serde::Deserialize<'de> for Even {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match u64::deserialize(deserializer)? {
+            n if n % 2 == 1 => Err(serde::de::Error::custom("odd")),
+            n => Ok(Even(n)),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    for json in [r#""x""#, "-1", "[1]"] {
+        assert!(
+            serde_json::from_str::<u64>(json)
+                .unwrap_err()
+                .is_recoverable(),
+            "{json}"
+        );
+    }
+    for json in [r#""x"#, "nul", "1 2", "1,"] {
+        assert!(
+            !serde_json::from_str::<u64>(json)
+                .unwrap_err()
+                .is_recoverable(),
+            "{json}"
+        );
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_custom_errors_are_skipped() {
+    let json = r#"{"a": 2, "b": 3, "c": "x", "d": 4}"#;
+    let mut report = SkipReport::default();
+    let map = SkippableMapSeed::<String, Even>::new()
+        .collect_skipped(&mut report)
+        .deserialize_recoverable(&mut serde_json::Deserializer::from_str(json))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert_eq!(map.0["d"].0, 4);
+    let skipped: Vec<_> = report.into_iter().map(|entry| entry.index).collect();
+    assert_eq!(skipped, [1, 2]);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_syntax_errors_are_returned() {
+    for json in [
+        r#"{"a": 2 "b": 4}"#,
+        r#"{"a": 2, "b": "#,
+        r#"{"a": [1, "b": 4}"#,
+    ] {
+        let err = SkippableMapSeed::<String, Even>::new()
+            .deserialize_recoverable(&mut serde_json::Deserializer::from_str(json))
+            .unwrap_err();
+        assert!(err.is_syntax() || err.is_eof(), "{json}: {err}");
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_filtered_values_are_decoded_but_not_skipped() {
+    let json = r#"{"a": 2, "b": 3, "c": 4}"#;
+    let mut report = SkipReport::default();
+    let map = SkippableMapSeed::<String, Even>::new()
+        .filter_keys(|key| key != "b")
+        .collect_skipped(&mut report)
+        .deserialize_recoverable(&mut serde_json::Deserializer::from_str(json))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert!(report.is_empty());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_max_entries_is_enforced() {
+    let json = r#"{"a": 2, "b": 3, "c": 4}"#;
+    let seed = |max| SkippableMapSeed::<String, Even>::new().max_entries(max);
+    assert!(seed(3)
+        .deserialize_recoverable(&mut serde_json::Deserializer::from_str(json))
+        .is_ok());
+    assert!(seed(2)
+        .deserialize_recoverable(&mut serde_json::Deserializer::from_str(json))
+        .is_err());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack() {
+    let number = rmp_serde::to_vec(&1000u64).unwrap();
+    assert!(rmp_serde::from_slice::<u8>(&number)
+        .unwrap_err()
+        .is_recoverable());
+    assert!(!rmp_serde::from_slice::<u64>(&number[..1])
+        .unwrap_err()
+        .is_recoverable());
+    // The string is left unread after its marker
+    let string = rmp_serde::to_vec(&"x").unwrap();
+    assert!(!rmp_serde::from_slice::<u64>(&string)
+        .unwrap_err()
+        .is_recoverable());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack_custom_errors_are_skipped() {
+    use std::collections::BTreeMap;
+
+    let input: BTreeMap<&str, u64> = [("a", 2), ("b", 3), ("c", 4)].into();
+    let bytes = rmp_serde::to_vec(&input).unwrap();
+    let map = SkippableMapSeed::<String, Even>::new()
+        .deserialize_recoverable(&mut rmp_serde::Deserializer::new(bytes.as_slice()))
+        .unwrap();
+    assert_eq!(map.0.len(), 2);
+    assert_eq!(map.0["c"].0, 4);
+
+    let result = SkippableMapSeed::<String, Even>::new()
+        .deserialize_recoverable(&mut rmp_serde::Deserializer::new(&bytes[..bytes.len() - 1]));
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn cbor() {
+    let mut string = Vec::new();
+    ciborium::into_writer(&"x", &mut string).unwrap();
+    let err = ciborium::from_reader::<u64, _>(string.as_slice()).unwrap_err();
+    assert!(err.is_recoverable());
+    let mut number = Vec::new();
+    ciborium::into_writer(&1000u64, &mut number).unwrap();
+    let err = ciborium::from_reader::<u64, _>(&number[..1]).unwrap_err();
+    assert!(!err.is_recoverable());
+}
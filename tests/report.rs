@@ -0,0 +1,50 @@
+use skippable_map::{SkippableMapWithReport, SkippedEntry};
+use std::collections::HashMap;
+
+fn report(json: &str) -> (HashMap<u32, u64>, Vec<SkippedEntry<u32>>) {
+    let map: SkippableMapWithReport<u32, u64> = serde_json::from_str(json).unwrap();
+    let (map, report) = map.into_parts();
+    (map.inner(), report.into_iter().collect())
+}
+
+#[test]
+fn a_key_which_fails_is_reported_without_it() {
+    let (map, skipped) = report(r#"{"1": 1, "x": 2, "3": 3}"#);
+    assert_eq!(map, HashMap::from([(1, 1), (3, 3)]));
+
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].index, 1);
+    assert_eq!(skipped[0].key, None);
+    assert!(
+        skipped[0].error.message().contains(r#""x""#),
+        "{}",
+        skipped[0].error
+    );
+}
+
+#[test]
+fn a_value_which_fails_is_reported_with_its_key() {
+    let (map, skipped) = report(r#"{"1": 1, "2": "y", "3": -3}"#);
+    assert_eq!(map, HashMap::from([(1, 1)]));
+
+    assert_eq!(skipped.len(), 2);
+    assert_eq!((skipped[0].index, skipped[0].key), (1, Some(2)));
+    assert!(
+        skipped[0].error.message().contains(r#""y""#),
+        "{}",
+        skipped[0].error
+    );
+    assert_eq!((skipped[1].index, skipped[1].key), (2, Some(3)));
+    assert!(
+        skipped[1].error.message().contains("-3"),
+        "{}",
+        skipped[1].error
+    );
+}
+
+#[test]
+fn nothing_is_reported_for_conforming_input() {
+    let (map, skipped) = report(r#"{"1": 1, "2": 2}"#);
+    assert_eq!(map.len(), 2);
+    assert!(skipped.is_empty());
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "json")]
+
+use serde_json::json;
+use skippable_map::SkippableMapWithRest;
+
+#[test]
+fn json_rest_keeps_skipped_entries() {
+    let json = r#"{"a": 1, "b": "x", "c": [1, {"d": null}], "e": -1.5, "f": 2}"#;
+    let (map, rest) = serde_json::from_str::<SkippableMapWithRest<String, u64>>(json)
+        .unwrap()
+        .into_parts();
+
+    assert_eq!(map.0.len(), 2);
+    assert_eq!(
+        serde_json::Value::Object(rest),
+        json!({"b": "x", "c": [1, {"d": null}], "e": -1.5})
+    );
+}
+
+#[test]
+fn yaml_rest_keeps_keys_which_fail_to_decode() {
+    let yaml = "1: 1\nx: 2\n3: [a]\n[4, 5]: 6\n";
+    let (map, rest) = serde_yaml::from_str::<SkippableMapWithRest<u32, u64>>(yaml)
+        .unwrap()
+        .into_parts();
+
+    assert_eq!(map.0.len(), 1);
+    assert_eq!(
+        serde_json::Value::Object(rest),
+        json!({"x": 2, "3": ["a"], "[4,5]": 6})
+    );
+}
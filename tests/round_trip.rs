@@ -0,0 +1,93 @@
+use skippable_map::SkippableMapWithExtras;
+
+#[test]
+fn json_unchanged() {
+    let json = r#"{"name":"x","a":1,"tags":["t"],"b":2,"nested":{"c":-1}}"#;
+    let map: SkippableMapWithExtras<String, u64> = serde_json::from_str(json).unwrap();
+    assert_eq!(map.extras_len(), 3);
+
+    let value: serde_json::Value = serde_json::from_str(json).unwrap();
+    let round_trip: serde_json::Value = serde_json::to_value(&map).unwrap();
+    assert_eq!(round_trip, value);
+}
+
+#[test]
+fn keys_which_fail_to_decode_are_kept() {
+    let json = r#"{"1":1,"x":"y","2":2}"#;
+    let map: SkippableMapWithExtras<u32, u64> = serde_json::from_str(json).unwrap();
+
+    let value: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_value(&map).unwrap(), value);
+}
+
+#[test]
+fn skipped_keys_which_are_set_are_written_once() {
+    let json = r#"{"a":"x","b":1}"#;
+    let mut map: SkippableMapWithExtras<String, u64> = serde_json::from_str(json).unwrap();
+    map.map.0.insert(String::from("a"), 5);
+
+    let written = serde_json::to_string(&map).unwrap();
+    assert_eq!(written.matches(r#""a""#).count(), 1, "{written}");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&written).unwrap(),
+        serde_json::json!({"a": 5, "b": 1})
+    );
+}
+
+#[test]
+fn repeated_keys_are_written_once() {
+    for (json, expected) in [
+        (r#"{"a":"x","a":1}"#, r#"{"a":1}"#),
+        (r#"{"a":1,"a":"x"}"#, r#"{"a":1}"#),
+        (r#"{"a":"x","a":"y"}"#, r#"{"a":"y"}"#),
+    ] {
+        let map: SkippableMapWithExtras<String, u64> = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&map).unwrap(), expected, "{json}");
+    }
+}
+
+#[test]
+fn cleared_extras_are_not_serialized() {
+    let json = r#"{"a":1,"b":"x"}"#;
+    let mut map: SkippableMapWithExtras<String, u64> = serde_json::from_str(json).unwrap();
+    map.clear_extras();
+
+    assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":1}"#);
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn order_is_kept_with_indexmap() {
+    use indexmap::IndexMap;
+
+    type Config = SkippableMapWithExtras<String, u64, IndexMap<String, u64>>;
+
+    let json = r#"{"name":"x","a":1,"tags":["t"],"b":2,"c":3,"nested":{"c":-1},"d":"e"}"#;
+    let mut map: Config = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+    map.map.0["b"] = 20;
+    map.map.0.insert(String::from("new"), 4);
+    assert_eq!(
+        serde_json::to_string(&map).unwrap(),
+        r#"{"name":"x","a":1,"tags":["t"],"b":20,"c":3,"nested":{"c":-1},"d":"e","new":4}"#
+    );
+
+    let yaml = "name: x\na: 1\ntags:\n- t\nb: 2\n";
+    let map: Config = serde_yaml::from_str(yaml).unwrap();
+    assert_eq!(serde_yaml::to_string(&map).unwrap(), yaml);
+}
+
+#[test]
+fn toml_datetimes_are_kept() {
+    let toml = "a = 1\nwhen = 1979-05-27T07:32:00Z\nname = \"x\"\n";
+    let map: SkippableMapWithExtras<String, u64> = toml::from_str(toml).unwrap();
+    assert_eq!(map.extras_len(), 2);
+
+    let round_trip = toml::to_string(&map).unwrap();
+    assert!(
+        round_trip.contains("when = 1979-05-27T07:32:00Z\n"),
+        "{round_trip}"
+    );
+    assert!(!round_trip.contains("[when]"), "{round_trip}");
+}
@@ -0,0 +1,74 @@
+use serde::de::DeserializeSeed;
+use skippable_map::{SkipReport, SkippableMap, SkippableMapSeed};
+
+const JSON: &str = r#"{"a": 1, "b": "x", "c": 2, "d": [3], "e": 4}"#;
+
+fn deserialize<'a>(
+    seed: SkippableMapSeed<'a, String, u64>,
+) -> Result<SkippableMap<String, u64>, serde_json::Error> {
+    seed.deserialize(&mut serde_json::Deserializer::from_str(JSON))
+}
+
+#[test]
+fn max_skips_boundary() {
+    // Two entries are skipped, which is allowed by any limit of at least two
+    for max in [2, 3] {
+        let map = deserialize(SkippableMapSeed::new().max_skips(max)).unwrap();
+        assert_eq!(map.0.len(), 3);
+    }
+
+    let err = deserialize(SkippableMapSeed::new().max_skips(1)).unwrap_err();
+    assert!(err.to_string().contains("entry 3"), "{err}");
+    assert!(err.to_string().contains("at most 1 entries"), "{err}");
+
+    let err = deserialize(SkippableMapSeed::new().max_skips(0)).unwrap_err();
+    assert!(err.to_string().contains("entry 1"), "{err}");
+}
+
+#[test]
+fn strict_is_max_skips_zero() {
+    let strict = deserialize(SkippableMapSeed::new().strict()).unwrap_err();
+    let zero = deserialize(SkippableMapSeed::new().max_skips(0)).unwrap_err();
+    assert_eq!(strict.to_string(), zero.to_string());
+}
+
+#[test]
+fn filtered_keys_are_not_counted_or_reported() {
+    let mut report = SkipReport::default();
+    let map = deserialize(
+        SkippableMapSeed::new()
+            .filter_keys(|key| key != "b" && key != "d")
+            .strict()
+            .collect_skipped(&mut report),
+    )
+    .unwrap();
+    assert_eq!(map.0.len(), 3);
+    assert!(report.is_empty());
+
+    // Only the skipped entry which passes the filter counts towards the limit
+    let mut report = SkipReport::default();
+    let map = deserialize(
+        SkippableMapSeed::new()
+            .allow_keys(["a".to_string(), "d".to_string()])
+            .max_skips(1)
+            .collect_skipped(&mut report),
+    )
+    .unwrap();
+    assert_eq!(map.0.len(), 1);
+    let skipped: Vec<_> = report
+        .iter()
+        .map(|entry| (entry.index, entry.key.as_deref()))
+        .collect();
+    assert_eq!(skipped, [(3, Some("d"))]);
+}
+
+#[test]
+fn skips_are_reported_with_their_position() {
+    let mut report = SkipReport::default();
+    deserialize(SkippableMapSeed::new().collect_skipped(&mut report)).unwrap();
+    let skipped: Vec<_> = report
+        .iter()
+        .map(|entry| (entry.index, entry.key.as_deref()))
+        .collect();
+    assert_eq!(skipped, [(1, Some("b")), (3, Some("d"))]);
+}
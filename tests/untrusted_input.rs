@@ -0,0 +1,101 @@
+use serde::de::{
+    value::{Error, MapAccessDeserializer, SeqAccessDeserializer},
+    Deserialize, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess,
+};
+use skippable_map::{SkippableMap, SkippableMapSeed, SkippableMapWithReport, SkippableVec};
+use std::{slice, vec};
+
+/// A map which claims to be far longer than it is, as a hostile length prefix would
+struct LyingMap(slice::Iter<'static, (&'static str, u64)>, Option<u64>);
+
+impl<'de> MapAccess<'de> for LyingMap {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some(&(key, value)) = self.0.next() else {
+            return Ok(None);
+        };
+        self.1 = Some(value);
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.1.take().unwrap().into_deserializer())
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::MAX)
+    }
+}
+
+struct LyingSeq(vec::IntoIter<u64>);
+
+impl<'de> SeqAccess<'de> for LyingSeq {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0
+            .next()
+            .map(|element| seed.deserialize(element.into_deserializer()))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::MAX)
+    }
+}
+
+fn lying_map(entries: &'static [(&'static str, u64)]) -> MapAccessDeserializer<LyingMap> {
+    MapAccessDeserializer::new(LyingMap(entries.iter(), None))
+}
+
+#[test]
+fn huge_size_hints_are_not_trusted() {
+    let entries = &[("a", 1), ("b", 2)];
+
+    let map = SkippableMap::<String, u64>::deserialize(lying_map(entries)).unwrap();
+    assert_eq!(map.0.len(), 2);
+
+    let map = SkippableMapWithReport::<String, u64>::deserialize(lying_map(entries)).unwrap();
+    assert_eq!(map.map.0.len(), 2);
+
+    let vec = SkippableVec::<u64>::deserialize(SeqAccessDeserializer::new(LyingSeq(
+        vec![1, 2, 3].into_iter(),
+    )))
+    .unwrap();
+    assert_eq!(vec.0, [1, 2, 3]);
+}
+
+#[test]
+fn max_entries() {
+    let entries = &[("a", 1), ("b", 2), ("c", 3)];
+
+    let map = SkippableMapSeed::<String, u64>::new()
+        .max_entries(3)
+        .deserialize(lying_map(entries))
+        .unwrap();
+    assert_eq!(map.0.len(), 3);
+
+    let err = SkippableMapSeed::<String, u64>::new()
+        .max_entries(2)
+        .deserialize(lying_map(entries))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "map has more than 2 entries");
+
+    // Entries which are filtered out still count
+    let err = SkippableMapSeed::<String, u64>::new()
+        .allow_keys([String::from("a")])
+        .max_entries(2)
+        .deserialize(lying_map(entries))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "map has more than 2 entries");
+}
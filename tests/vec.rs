@@ -0,0 +1,29 @@
+use skippable_map::SkippableVec;
+
+#[test]
+fn elements_which_fail_are_skipped_in_order() {
+    let json = r#"[1, "a", 2, -1, null, [3], {"b": 4}, 3.5, 18446744073709551616, 3]"#;
+    let vec: SkippableVec<u64> = serde_json::from_str(json).unwrap();
+    assert_eq!(vec.0, [1, 2, 3]);
+
+    let vec: SkippableVec<(String, u8)> =
+        serde_yaml::from_str("- [a, 1]\n- [b, 256]\n- [c]\n- [d, 2]\n").unwrap();
+    assert_eq!(vec.0, [("a".to_string(), 1), ("d".to_string(), 2)]);
+}
+
+#[test]
+fn broken_input_is_an_error() {
+    for json in [
+        "[1, 2",
+        "[1,, 2]",
+        r#"[1, "a]"#,
+        "[1, [2}]",
+        r#"{"a": 1}"#,
+        "1",
+    ] {
+        assert!(
+            serde_json::from_str::<SkippableVec<u64>>(json).is_err(),
+            "{json}"
+        );
+    }
+}